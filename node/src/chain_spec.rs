@@ -29,7 +29,7 @@ pub type ChainSpec = sc_service::GenericChainSpec;
 /// 包括代币小数位数和代币符号
 fn props() -> Properties {
 	let mut properties = Properties::new();
-	properties.insert("tokenDecimals".to_string(), 0.into());  // 代币小数位数
+	properties.insert("tokenDecimals".to_string(), 12.into()); // 代币小数位数
 	properties.insert("tokenSymbol".to_string(), "MINI".into()); // 代币符号
 	properties
 }
@@ -45,3 +45,15 @@ pub fn development_chain_spec() -> Result<ChainSpec, String> {
 		.with_properties(props())  // 链属性
 		.build())
 }
+
+/// 创建本地测试网络链规范
+/// 由 Alice 和 Bob 在 `--consensus aura` 模式下轮流出块
+pub fn local_chain_spec() -> Result<ChainSpec, String> {
+	Ok(ChainSpec::builder(WASM_BINARY.expect("Development wasm not available"), Default::default())
+		.with_name("Local Testnet")  // 链名称
+		.with_id("local_testnet")    // 链 ID
+		.with_chain_type(ChainType::Local)  // 链类型：本地测试网络
+		.with_genesis_config_preset_name(sp_genesis_builder::LOCAL_TESTNET_RUNTIME_PRESET)  // 创世配置预设
+		.with_properties(props())  // 链属性
+		.build())
+}
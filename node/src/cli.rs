@@ -15,41 +15,248 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::dev_timestamp::DevTimestampMode;
+use minimal_template_runtime::MinimumPeriod;
 use polkadot_sdk::*;
+use std::time::Duration;
 
 /// 共识机制类型
 /// 定义节点支持的共识算法类型
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Consensus {
-	/// 手动出块，参数为出块间隔（毫秒）
-	ManualSeal(u64),
+	/// 手动出块，参数为出块间隔（毫秒），不能小于运行时的 `MinimumPeriod`
+	/// 为 `None` 时不启动定时器，只通过 `engine_createBlock` RPC 出块
+	ManualSeal(Option<u64>),
 	/// 即时出块（收到交易立即出块）
 	InstantSeal,
+	/// Aura 共识：创世配置中的出块者按 slot 轮流出块
+	Aura,
+	/// BABE 共识：出块者通过 VRF 竞争 slot
+	Babe,
+	/// 工作量证明：矿工线程搜索满足链上难度的 nonce，不需要出块者密钥
+	Pow,
 	/// 无共识（仅用于测试）
 	None,
 }
 
-/// 从字符串解析共识类型
+impl Consensus {
+	/// 所有支持的格式，用于错误提示
+	const VALID_MODES: &'static str = "`manual-seal`, `manual-seal:<duration>` (e.g. `manual-seal:3s`, \
+		`manual-seal:1500ms`), `manual-seal-<ms>`, `manual-seal-0`, `instant-seal`, `aura`, `babe`, `pow`, `none`";
+}
+
+/// 解析时间间隔：`<n>ms`、`<n>s`、`<n>m`，没有单位时按毫秒计算，返回毫秒数
+fn parse_duration(s: &str, what: &str) -> Result<u64, String> {
+	let (number, unit_ms) = if let Some(number) = s.strip_suffix("ms") {
+		(number, 1)
+	} else if let Some(number) = s.strip_suffix('s') {
+		(number, 1_000)
+	} else if let Some(number) = s.strip_suffix('m') {
+		(number, 60_000)
+	} else {
+		(s, 1)
+	};
+	let block_time = number
+		.parse::<u64>()
+		.ok()
+		.and_then(|number| number.checked_mul(unit_ms))
+		.ok_or_else(|| format!("invalid {what} `{s}`, expected e.g. `3s`, `500ms` or `3000`"))?;
+	if block_time == 0 {
+		return Err(format!("{what} must be greater than zero"));
+	}
+	Ok(block_time)
+}
+
+/// 解析手动出块间隔，不能小于运行时的 `MinimumPeriod`，否则时间戳 pallet 会拒绝新区块
+fn parse_block_time(s: &str) -> Result<u64, String> {
+	let block_time = parse_duration(s, "block time")
+		.map_err(|err| format!("{err}, use `manual-seal` to seal blocks only via RPC"))?;
+	if block_time < MinimumPeriod::get() {
+		return Err(format!(
+			"block time `{s}` is shorter than the runtime's `MinimumPeriod` of {}ms",
+			MinimumPeriod::get()
+		));
+	}
+	Ok(block_time)
+}
+
+/// 手动出块定时器的参数
+#[derive(Debug, Clone, Copy, clap::Args)]
+pub struct SealParams {
+	/// 手动出块定时器在交易池为空时是否仍然出空块，设为 `false` 时只在有可打包交易时出块
+	#[clap(long, default_value_t = true, action = clap::ArgAction::Set)]
+	pub seal_empty_blocks: bool,
+
+	/// `--seal-empty-blocks=false` 时两个区块之间的最大空闲间隔（如 `10m`），超过后出一个空的心跳块。
+	/// 未指定时交易池为空就一直不出块
+	#[clap(long, value_parser = |s: &str| parse_duration(s, "idle interval"))]
+	pub max_idle_interval: Option<u64>,
+}
+
+impl SealParams {
+	/// 检查参数是否适用于 `consensus`：只有定时手动出块会用到这些参数，其他组合在启动时报错而不是被忽略
+	pub fn validate(&self, consensus: &Consensus) -> Result<(), String> {
+		let customized = !self.seal_empty_blocks || self.max_idle_interval.is_some();
+		if customized && !matches!(consensus, Consensus::ManualSeal(Some(_))) {
+			return Err("`--seal-empty-blocks` and `--max-idle-interval` only apply to the manual \
+				seal timer, use `--consensus manual-seal:<duration>`"
+				.into());
+		}
+		if self.max_idle_interval.is_some() && self.seal_empty_blocks {
+			return Err("`--max-idle-interval` requires `--seal-empty-blocks=false`".into());
+		}
+		Ok(())
+	}
+
+	/// 手动出块定时器在一次 tick 上是否出块
+	/// `ready` 为交易池中可打包的交易数，`idle` 为距离上一次导入区块的时间
+	pub fn should_seal(&self, ready: usize, idle: Duration) -> SealDecision {
+		if self.seal_empty_blocks {
+			return SealDecision::Seal { create_empty: true };
+		}
+		if ready > 0 {
+			// 交易可能已被 RPC 出的块打包，此时不再出空块
+			return SealDecision::Seal { create_empty: false };
+		}
+		match self.max_idle_interval {
+			// 心跳块
+			Some(max) if idle >= Duration::from_millis(max) =>
+				SealDecision::Seal { create_empty: true },
+			_ => SealDecision::Skip,
+		}
+	}
+}
+
+/// 手动出块定时器在一次 tick 上的决定
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SealDecision {
+	/// 跳过本次出块
+	Skip,
+	/// 出块，`create_empty` 为 `false` 时交易池中没有可打包的交易就不出块
+	Seal {
+		/// 是否允许出空块
+		create_empty: bool,
+	},
+}
+
+/// 从字符串解析共识类型，不区分大小写
 /// 支持的格式：
+/// - "manual-seal"、"manual-seal-0" -> ManualSeal(None)
+/// - "manual-seal:3s"、"manual-seal:1500ms"、"manual-seal:1m" -> ManualSeal(Some(<毫秒>))
+/// - "manual-seal-3000" -> ManualSeal(Some(3000))
 /// - "instant-seal" -> InstantSeal
-/// - "manual-seal-3000" -> ManualSeal(3000)
+/// - "aura" -> Aura
+/// - "babe" -> Babe
+/// - "pow" -> Pow
 /// - "none" -> None
 impl std::str::FromStr for Consensus {
 	type Err = String;
 
-	fn from_str(s: &str) -> Result<Self, Self::Err> {
-		Ok(if s == "instant-seal" {
-			Consensus::InstantSeal
-		} else if let Some(block_time) = s.strip_prefix("manual-seal-") {
-			Consensus::ManualSeal(block_time.parse().map_err(|_| "invalid block time")?)
-		} else if s.to_lowercase() == "none" {
-			Consensus::None
-		} else {
-			return Err("incorrect consensus identifier".into());
+	fn from_str(input: &str) -> Result<Self, Self::Err> {
+		let s = input.trim().to_ascii_lowercase();
+		// `manual-seal-0` 是只通过 RPC 出块的别名，其他写法的零间隔仍然报错
+		if s == "manual-seal-0" {
+			return Ok(Consensus::ManualSeal(None));
+		}
+		if let Some(block_time) =
+			s.strip_prefix("manual-seal:").or_else(|| s.strip_prefix("manual-seal-"))
+		{
+			return Ok(Consensus::ManualSeal(Some(parse_block_time(block_time)?)));
+		}
+
+		Ok(match s.as_str() {
+			"manual-seal" => Consensus::ManualSeal(None),
+			"instant-seal" => Consensus::InstantSeal,
+			"aura" => Consensus::Aura,
+			"babe" => Consensus::Babe,
+			"pow" => Consensus::Pow,
+			"none" => Consensus::None,
+			_ =>
+				return Err(format!(
+					"unknown consensus `{input}`, expected one of {}",
+					Consensus::VALID_MODES
+				)),
 		})
 	}
 }
 
+/// 输出可以被 [`FromStr`](std::str::FromStr) 重新解析的规范格式
+impl std::fmt::Display for Consensus {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		match self {
+			Consensus::ManualSeal(None) => write!(f, "manual-seal"),
+			Consensus::ManualSeal(Some(ms)) if ms % 1_000 == 0 =>
+				write!(f, "manual-seal:{}s", ms / 1_000),
+			Consensus::ManualSeal(Some(ms)) => write!(f, "manual-seal:{ms}ms"),
+			Consensus::InstantSeal => write!(f, "instant-seal"),
+			Consensus::Aura => write!(f, "aura"),
+			Consensus::Babe => write!(f, "babe"),
+			Consensus::Pow => write!(f, "pow"),
+			Consensus::None => write!(f, "none"),
+		}
+	}
+}
+
+/// 最终性机制类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Finality {
+	/// 不运行最终性组件，由出块机制决定（手动出块会立即最终化新区块）
+	None,
+	/// 运行 GRANDPA 投票者，由验证人投票最终化区块
+	Grandpa,
+}
+
+/// 手动出块和即时出块模式下的延迟最终化
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FinalityDelay {
+	/// 区块导入若干毫秒后最终化，不能为零
+	Millis(u64),
+	/// 区块之上再导入若干个区块后最终化，不能为零
+	Blocks(u32),
+}
+
+impl FinalityDelay {
+	/// 所有支持的格式，用于错误提示
+	const VALID_FORMATS: &'static str = "expected `<n>ms`, `<n>s`, `<n>m` or `<n>blocks`";
+}
+
+/// 从字符串解析延迟最终化，不区分大小写
+/// 支持的格式：
+/// - "6s"、"500ms"、"1m" -> Millis(<毫秒>)
+/// - "3blocks"、"1block" -> Blocks(3)、Blocks(1)
+impl std::str::FromStr for FinalityDelay {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let s = s.trim().to_ascii_lowercase();
+		if let Some(blocks) = s.strip_suffix("blocks").or_else(|| s.strip_suffix("block")) {
+			let blocks = blocks
+				.parse::<u32>()
+				.map_err(|_| format!("invalid finality delay `{s}`, {}", Self::VALID_FORMATS))?;
+			if blocks == 0 {
+				return Err("finality delay must be greater than zero".into());
+			}
+			return Ok(FinalityDelay::Blocks(blocks));
+		}
+		// 没有单位时无法区分时间和区块数
+		if !s.ends_with(|c: char| c.is_ascii_alphabetic()) {
+			return Err(format!("invalid finality delay `{s}`, {}", Self::VALID_FORMATS));
+		}
+		parse_duration(&s, "finality delay").map(FinalityDelay::Millis)
+	}
+}
+
+/// 输出可以被 [`FromStr`](std::str::FromStr) 重新解析的规范格式
+impl std::fmt::Display for FinalityDelay {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		match self {
+			FinalityDelay::Millis(ms) if ms % 1_000 == 0 => write!(f, "{}s", ms / 1_000),
+			FinalityDelay::Millis(ms) => write!(f, "{ms}ms"),
+			FinalityDelay::Blocks(blocks) => write!(f, "{blocks}blocks"),
+		}
+	}
+}
+
 /// 命令行参数结构体
 /// 定义节点支持的所有命令行选项
 #[derive(Debug, clap::Parser)]
@@ -58,10 +265,34 @@ pub struct Cli {
 	#[command(subcommand)]
 	pub subcommand: Option<Subcommand>,
 
-	/// 共识机制类型，默认为手动出块，间隔 3000 毫秒
-	#[clap(long, default_value = "manual-seal-3000")]
+	/// 共识机制类型，默认为手动出块，间隔 3 秒
+	/// 可选值：`manual-seal`、`manual-seal:<间隔>`（如 `3s`、`1500ms`，不能小于 1500 毫秒）、`instant-seal`、`aura`、`babe`、`pow`、`none`
+	#[clap(long, default_value = "manual-seal:3s")]
 	pub consensus: Consensus,
 
+	/// 最终性机制，默认不运行最终性组件
+	#[clap(long, value_enum, default_value_t = Finality::None)]
+	pub finality: Finality,
+
+	/// 手动出块和即时出块模式下延迟最终化区块：`<n>s`、`<n>ms` 表示导入一段时间后，`<n>blocks` 表示之上再导入 n 个区块后。
+	/// 未指定时手动出块立即最终化新区块，即时出块不最终化区块；不能与 `--finality grandpa` 同时使用
+	#[clap(long)]
+	pub finality_delay: Option<FinalityDelay>,
+
+	/// 手动出块和即时出块模式下区块时间戳的来源：
+	/// `system`、`fixed-start=<ms>,step=<ms>`（每个区块前进 `step` 毫秒）或 `follow-slot`（每个区块前进一个 slot）
+	/// 其他共识模式只能使用 `system`
+	#[clap(long, default_value = "system")]
+	pub dev_timestamp: DevTimestampMode,
+
+	/// 手动出块定时器的参数
+	#[clap(flatten)]
+	pub seal: SealParams,
+
+	/// `--consensus pow` 模式下的挖矿线程数，为 0 时只同步和校验区块而不挖矿
+	#[clap(long, default_value_t = 1)]
+	pub pow_threads: usize,
+
 	/// 运行节点的通用参数（如 --dev, --tmp 等）
 	#[clap(flatten)]
 	pub run: sc_cli::RunCmd,
@@ -105,4 +336,196 @@ pub enum Subcommand {
 
 	/// 数据库元列信息
 	ChainInfo(sc_cli::ChainInfoCmd),
+
+	/// 基准测试子命令（pallet、区块、存储、开销和机器性能）
+	#[command(subcommand)]
+	Benchmark(frame_benchmarking_cli::BenchmarkCmd),
+
+	/// try-runtime 已迁移到独立的 CLI，参见 <https://github.com/paritytech/try-runtime-cli>
+	#[cfg(feature = "try-runtime")]
+	TryRuntime,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn parse(s: &str) -> Result<Consensus, String> {
+		s.parse()
+	}
+
+	#[test]
+	fn parses_simple_modes() {
+		assert_eq!(parse("instant-seal"), Ok(Consensus::InstantSeal));
+		assert_eq!(parse("aura"), Ok(Consensus::Aura));
+		assert_eq!(parse("babe"), Ok(Consensus::Babe));
+		assert_eq!(parse("pow"), Ok(Consensus::Pow));
+		assert_eq!(parse("none"), Ok(Consensus::None));
+		assert_eq!(parse("manual-seal"), Ok(Consensus::ManualSeal(None)));
+		assert_eq!(parse("manual-seal-0"), Ok(Consensus::ManualSeal(None)));
+	}
+
+	#[test]
+	fn parsing_is_case_insensitive() {
+		assert_eq!(parse("None"), Ok(Consensus::None));
+		assert_eq!(parse("AURA"), Ok(Consensus::Aura));
+		assert_eq!(parse("Instant-Seal"), Ok(Consensus::InstantSeal));
+		assert_eq!(parse("Manual-Seal:3S"), Ok(Consensus::ManualSeal(Some(3_000))));
+	}
+
+	#[test]
+	fn parses_manual_seal_durations() {
+		assert_eq!(parse("manual-seal:3s"), Ok(Consensus::ManualSeal(Some(3_000))));
+		assert_eq!(parse("manual-seal:1500ms"), Ok(Consensus::ManualSeal(Some(1_500))));
+		assert_eq!(parse("manual-seal:1m"), Ok(Consensus::ManualSeal(Some(60_000))));
+		assert_eq!(parse("manual-seal:1500"), Ok(Consensus::ManualSeal(Some(1_500))));
+	}
+
+	#[test]
+	fn parses_legacy_manual_seal_format() {
+		assert_eq!(parse("manual-seal-3000"), Ok(Consensus::ManualSeal(Some(3_000))));
+		assert_eq!(parse("manual-seal-2s"), Ok(Consensus::ManualSeal(Some(2_000))));
+	}
+
+	#[test]
+	fn rejects_zero_block_time() {
+		for s in ["manual-seal:0", "manual-seal:0s", "manual-seal:0ms", "manual-seal-0s"] {
+			let err = parse(s).unwrap_err();
+			assert!(err.contains("greater than zero"), "{s}: {err}");
+		}
+	}
+
+	#[test]
+	fn rejects_block_time_below_minimum_period() {
+		for s in ["manual-seal:500ms", "manual-seal:1499", "manual-seal-1s"] {
+			let err = parse(s).unwrap_err();
+			assert!(err.contains("MinimumPeriod"), "{s}: {err}");
+		}
+	}
+
+	#[test]
+	fn rejects_invalid_block_time() {
+		for s in ["manual-seal:", "manual-seal:abc", "manual-seal:3h", "manual-seal:-1s"] {
+			assert!(parse(s).unwrap_err().contains("invalid block time"), "{s}");
+		}
+		// 溢出 `u64` 的间隔
+		assert!(parse("manual-seal:18446744073709551615m").is_err());
+	}
+
+	#[test]
+	fn unknown_mode_lists_valid_modes() {
+		let err = parse(" GrandPa").unwrap_err();
+		assert!(err.contains("unknown consensus ` GrandPa`"), "{err}");
+		for mode in ["manual-seal", "instant-seal", "aura", "babe", "pow", "none"] {
+			assert!(err.contains(mode), "{err} does not mention {mode}");
+		}
+	}
+
+	#[test]
+	fn display_round_trips() {
+		for consensus in [
+			Consensus::ManualSeal(None),
+			Consensus::ManualSeal(Some(3_000)),
+			Consensus::ManualSeal(Some(1_500)),
+			Consensus::InstantSeal,
+			Consensus::Aura,
+			Consensus::Babe,
+			Consensus::Pow,
+			Consensus::None,
+		] {
+			assert_eq!(parse(&consensus.to_string()), Ok(consensus));
+		}
+		assert_eq!(Consensus::ManualSeal(Some(3_000)).to_string(), "manual-seal:3s");
+		assert_eq!(Consensus::ManualSeal(Some(1_500)).to_string(), "manual-seal:1500ms");
+	}
+
+	#[test]
+	fn parses_finality_delays() {
+		let parse = |s: &str| s.parse::<FinalityDelay>();
+		assert_eq!(parse("6s"), Ok(FinalityDelay::Millis(6_000)));
+		assert_eq!(parse("500ms"), Ok(FinalityDelay::Millis(500)));
+		assert_eq!(parse("1m"), Ok(FinalityDelay::Millis(60_000)));
+		assert_eq!(parse("6S"), Ok(FinalityDelay::Millis(6_000)));
+		assert_eq!(parse("3blocks"), Ok(FinalityDelay::Blocks(3)));
+		assert_eq!(parse("1block"), Ok(FinalityDelay::Blocks(1)));
+	}
+
+	#[test]
+	fn rejects_invalid_finality_delays() {
+		for s in ["0s", "0ms", "0blocks"] {
+			let err = s.parse::<FinalityDelay>().unwrap_err();
+			assert!(err.contains("greater than zero"), "{s}: {err}");
+		}
+		for s in ["", "6", "blocks", "abc", "-1s", "3 blocks", "3h"] {
+			assert!(s.parse::<FinalityDelay>().is_err(), "{s}");
+		}
+	}
+
+	#[test]
+	fn finality_delay_display_round_trips() {
+		for delay in [
+			FinalityDelay::Millis(6_000),
+			FinalityDelay::Millis(500),
+			FinalityDelay::Millis(1_500),
+			FinalityDelay::Blocks(3),
+		] {
+			assert_eq!(delay.to_string().parse(), Ok(delay));
+		}
+		assert_eq!(FinalityDelay::Millis(6_000).to_string(), "6s");
+		assert_eq!(FinalityDelay::Millis(500).to_string(), "500ms");
+	}
+
+	fn seal_params(seal_empty_blocks: bool, max_idle_interval: Option<u64>) -> SealParams {
+		SealParams { seal_empty_blocks, max_idle_interval }
+	}
+
+	#[test]
+	fn seal_params_only_apply_to_the_seal_timer() {
+		let timer = Consensus::ManualSeal(Some(3_000));
+		assert_eq!(seal_params(true, None).validate(&timer), Ok(()));
+		assert_eq!(seal_params(false, None).validate(&timer), Ok(()));
+		assert_eq!(seal_params(false, Some(60_000)).validate(&timer), Ok(()));
+
+		// 默认参数适用于所有模式
+		for consensus in [Consensus::ManualSeal(None), Consensus::InstantSeal, Consensus::Aura] {
+			assert_eq!(seal_params(true, None).validate(&consensus), Ok(()));
+			assert!(seal_params(false, None).validate(&consensus).is_err());
+			assert!(seal_params(false, Some(60_000)).validate(&consensus).is_err());
+		}
+	}
+
+	#[test]
+	fn max_idle_interval_requires_skipping_empty_blocks() {
+		let err = seal_params(true, Some(60_000))
+			.validate(&Consensus::ManualSeal(Some(3_000)))
+			.unwrap_err();
+		assert!(err.contains("--seal-empty-blocks=false"), "{err}");
+	}
+
+	#[test]
+	fn should_seal_decisions() {
+		let idle = Duration::from_secs(1);
+		let seal = |create_empty| SealDecision::Seal { create_empty };
+
+		// 默认总是出块，交易池为空时出空块
+		assert_eq!(seal_params(true, None).should_seal(0, idle), seal(true));
+		assert_eq!(seal_params(true, None).should_seal(2, idle), seal(true));
+
+		// 不出空块时，只在有可打包的交易时出块
+		assert_eq!(seal_params(false, None).should_seal(0, Duration::MAX), SealDecision::Skip);
+		assert_eq!(seal_params(false, None).should_seal(1, idle), seal(false));
+
+		// 空闲超过最大间隔时出心跳块
+		let params = seal_params(false, Some(10_000));
+		assert_eq!(params.should_seal(0, Duration::from_millis(9_999)), SealDecision::Skip);
+		assert_eq!(params.should_seal(0, Duration::from_millis(10_000)), seal(true));
+		assert_eq!(params.should_seal(1, idle), seal(false));
+	}
+
+	#[test]
+	fn default_consensus_parses() {
+		use clap::Parser;
+		let cli = Cli::try_parse_from(["minimal-template-node"]).unwrap();
+		assert_eq!(cli.consensus, Consensus::ManualSeal(Some(3_000)));
+	}
 }
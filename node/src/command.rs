@@ -20,7 +20,11 @@ use crate::{
 	cli::{Cli, Subcommand},
 	service,
 };
-use polkadot_sdk::{sc_cli::SubstrateCli, sc_service::PartialComponents, *};
+use frame_benchmarking_cli::{BenchmarkCmd, SUBSTRATE_REFERENCE_HARDWARE};
+use minimal_template_runtime::interface::OpaqueBlock as Block;
+use polkadot_sdk::{
+	sc_cli::SubstrateCli, sc_service::PartialComponents, sp_runtime::traits::HashingFor, *,
+};
 
 /// 实现 SubstrateCli trait，提供 CLI 的基本信息
 impl SubstrateCli for Cli {
@@ -56,10 +60,12 @@ impl SubstrateCli for Cli {
 
 	/// 加载链规范
 	/// - "dev" -> 开发链规范
+	/// - "local" -> 本地测试网络链规范
 	/// - 其他 -> 从 JSON 文件加载
 	fn load_spec(&self, id: &str) -> Result<Box<dyn sc_service::ChainSpec>, String> {
 		Ok(match id {
 			"dev" => Box::new(chain_spec::development_chain_spec()?),
+			"local" => Box::new(chain_spec::local_chain_spec()?),
 			path =>
 				Box::new(chain_spec::ChainSpec::from_json_file(std::path::PathBuf::from(path))?),
 		})
@@ -82,7 +88,7 @@ pub fn run() -> sc_cli::Result<()> {
 			let runner = cli.create_runner(cmd)?;
 			runner.async_run(|config| {
 				let PartialComponents { client, task_manager, import_queue, .. } =
-					service::new_partial(&config)?;
+					service::new_partial(&config, &cli.consensus)?;
 				Ok((cmd.run(client, import_queue), task_manager))
 			})
 		},
@@ -93,14 +99,16 @@ pub fn run() -> sc_cli::Result<()> {
 		Some(Subcommand::ExportBlocks(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
 			runner.async_run(|config| {
-				let PartialComponents { client, task_manager, .. } = service::new_partial(&config)?;
+				let PartialComponents { client, task_manager, .. } =
+					service::new_partial(&config, &cli.consensus)?;
 				Ok((cmd.run(client, config.database), task_manager))
 			})
 		},
 		Some(Subcommand::ExportState(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
 			runner.async_run(|config| {
-				let PartialComponents { client, task_manager, .. } = service::new_partial(&config)?;
+				let PartialComponents { client, task_manager, .. } =
+					service::new_partial(&config, &cli.consensus)?;
 				Ok((cmd.run(client, config.chain_spec), task_manager))
 			})
 		},
@@ -108,7 +116,7 @@ pub fn run() -> sc_cli::Result<()> {
 			let runner = cli.create_runner(cmd)?;
 			runner.async_run(|config| {
 				let PartialComponents { client, task_manager, import_queue, .. } =
-					service::new_partial(&config)?;
+					service::new_partial(&config, &cli.consensus)?;
 				Ok((cmd.run(client, import_queue), task_manager))
 			})
 		},
@@ -120,28 +128,92 @@ pub fn run() -> sc_cli::Result<()> {
 			let runner = cli.create_runner(cmd)?;
 			runner.async_run(|config| {
 				let PartialComponents { client, task_manager, backend, .. } =
-					service::new_partial(&config)?;
+					service::new_partial(&config, &cli.consensus)?;
 				Ok((cmd.run(client, backend, None), task_manager))
 			})
 		},
 		Some(Subcommand::ChainInfo(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
 			runner.sync_run(|config| {
-				cmd.run::<minimal_template_runtime::interface::OpaqueBlock>(&config)
+				cmd.run::<Block>(&config)
 			})
 		},
-		/// 无子命令时，启动节点
+		Some(Subcommand::Benchmark(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.sync_run(|config| {
+				// 节点决定支持哪些基准测试子命令
+				match cmd {
+					BenchmarkCmd::Pallet(cmd) => {
+						if !cfg!(feature = "runtime-benchmarks") {
+							return Err("Runtime benchmarking wasn't enabled when building the node. \
+								You can enable it with `--features runtime-benchmarks`."
+								.into());
+						}
+						cmd.run_with_spec::<HashingFor<Block>, ()>(Some(config.chain_spec))
+					},
+					BenchmarkCmd::Block(cmd) => {
+						let PartialComponents { client, .. } =
+							service::new_partial(&config, &cli.consensus)?;
+						cmd.run(client)
+					},
+					#[cfg(not(feature = "runtime-benchmarks"))]
+					BenchmarkCmd::Storage(_) => Err("Storage benchmarking can be enabled with \
+						`--features runtime-benchmarks`."
+						.into()),
+					#[cfg(feature = "runtime-benchmarks")]
+					BenchmarkCmd::Storage(cmd) => {
+						let PartialComponents { client, backend, .. } =
+							service::new_partial(&config, &cli.consensus)?;
+						let db = backend.expose_db();
+						let storage = backend.expose_storage();
+						let shared_cache = backend.expose_shared_trie_cache();
+						cmd.run(config, client, db, storage, shared_cache)
+					},
+					BenchmarkCmd::Overhead(cmd) => cmd
+						.run_with_default_builder_and_spec::<Block, ()>(Some(config.chain_spec)),
+					BenchmarkCmd::Extrinsic(_) => Err("Extrinsic benchmarking is not supported \
+						by this node, use `benchmark overhead` instead."
+						.into()),
+					BenchmarkCmd::Machine(cmd) =>
+						cmd.run(&config, SUBSTRATE_REFERENCE_HARDWARE.clone()),
+				}
+			})
+		},
+		#[cfg(feature = "try-runtime")]
+		Some(Subcommand::TryRuntime) => Err(
+			"The `try-runtime` subcommand has been migrated to a standalone CLI \
+			(https://github.com/paritytech/try-runtime-cli). See runtime/README.md for usage."
+				.into(),
+		),
+		// 无子命令时，启动节点
 		None => {
 			let runner = cli.create_runner(&cli.run)?;
 			runner.run_node_until_exit(|config| async move {
 				// 根据网络后端类型选择相应的实现
 				match config.network.network_backend {
-					sc_network::config::NetworkBackendType::Libp2p =>
-						service::new_full::<sc_network::NetworkWorker<_, _>>(config, cli.consensus)
-							.map_err(sc_cli::Error::Service),
+					sc_network::config::NetworkBackendType::Libp2p => service::new_full::<
+						sc_network::NetworkWorker<_, _>,
+					>(
+						config,
+						cli.consensus,
+						cli.finality,
+						cli.finality_delay,
+						cli.dev_timestamp,
+						cli.seal,
+						cli.pow_threads,
+					)
+					.map_err(sc_cli::Error::Service),
 					sc_network::config::NetworkBackendType::Litep2p => service::new_full::<
 						sc_network::Litep2pNetworkBackend,
-					>(config, cli.consensus)
+					>(
+						config,
+						cli.consensus,
+						cli.finality,
+						cli.finality_delay,
+						cli.dev_timestamp,
+						cli.seal,
+						cli.pow_threads,
+					)
 					.map_err(sc_cli::Error::Service),
 				}
 			})
@@ -0,0 +1,320 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 手动出块和即时出块模式下的时间戳 inherent
+//!
+//! 通过 `--dev-timestamp` 选择区块时间戳的来源，使测试中的区块可以复现，
+//! 并提供 `dev_advanceTime` RPC 让时间向前跳跃
+
+use crate::service::FullClient;
+use codec::Decode;
+use jsonrpsee::{core::RpcResult, proc_macros::rpc, Extensions};
+use minimal_template_runtime::{
+	interface::OpaqueBlock as Block, MinimumPeriod, MILLI_SECS_PER_BLOCK,
+};
+use polkadot_sdk::{
+	sc_client_api::StorageProvider,
+	sp_core::{storage::StorageKey, twox_128},
+	sp_runtime::traits::Block as BlockT,
+	*,
+};
+use std::{
+	fmt,
+	str::FromStr,
+	sync::{
+		atomic::{AtomicU64, Ordering},
+		Arc,
+	},
+	time::{SystemTime, UNIX_EPOCH},
+};
+
+/// 区块时间戳的来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DevTimestampMode {
+	/// 使用系统时间
+	System,
+	/// 第一个区块使用 `start`，之后每个区块比父区块前进 `step` 毫秒
+	Fixed {
+		/// 第一个区块的时间戳（毫秒）
+		start: u64,
+		/// 相邻区块的时间间隔（毫秒），不能小于运行时的 `MinimumPeriod`
+		step: u64,
+	},
+	/// 第一个区块使用对齐到 slot 边界的系统时间，之后每个区块前进一个 slot（`MILLI_SECS_PER_BLOCK`）
+	FollowSlot,
+}
+
+/// 支持的格式：
+/// - "system" -> System
+/// - "fixed-start=<ms>,step=<ms>" -> Fixed
+/// - "follow-slot" -> FollowSlot
+impl FromStr for DevTimestampMode {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		if s == "system" {
+			return Ok(DevTimestampMode::System);
+		}
+		if s == "follow-slot" {
+			return Ok(DevTimestampMode::FollowSlot);
+		}
+
+		let Some(params) = s.strip_prefix("fixed-") else {
+			return Err(format!(
+				"invalid timestamp mode `{s}`, expected `system`, `fixed-start=<ms>,step=<ms>` or `follow-slot`"
+			));
+		};
+		let (mut start, mut step) = (None, None);
+		for param in params.split(',') {
+			let (key, value) = param
+				.split_once('=')
+				.ok_or_else(|| format!("expected `key=value`, found `{param}`"))?;
+			let value = value.parse::<u64>().map_err(|_| format!("invalid number `{value}`"))?;
+			match key {
+				"start" => start = Some(value),
+				"step" => step = Some(value),
+				_ => return Err(format!("unknown parameter `{key}`, expected `start` or `step`")),
+			}
+		}
+		let start = start.ok_or("missing `start=<ms>`")?;
+		let step = step.ok_or("missing `step=<ms>`")?;
+		if step < MinimumPeriod::get() {
+			return Err(format!(
+				"`step` must be at least {}ms, the runtime's `MinimumPeriod`",
+				MinimumPeriod::get()
+			));
+		}
+		Ok(DevTimestampMode::Fixed { start, step })
+	}
+}
+
+impl fmt::Display for DevTimestampMode {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			DevTimestampMode::System => write!(f, "system"),
+			DevTimestampMode::Fixed { start, step } => write!(f, "fixed-start={start},step={step}"),
+			DevTimestampMode::FollowSlot => write!(f, "follow-slot"),
+		}
+	}
+}
+
+/// 手动出块和即时出块共用的时钟
+/// 与 `dev_advanceTime` RPC 共享跳跃的时间
+#[derive(Clone)]
+pub struct DevClock {
+	mode: DevTimestampMode,
+	/// `System` 模式下为累计跳跃的时间；其他模式下为尚未应用到区块上的跳跃时间
+	offset: Arc<AtomicU64>,
+}
+
+impl DevClock {
+	/// 创建指定模式的时钟
+	pub fn new(mode: DevTimestampMode) -> Self {
+		Self { mode, offset: Default::default() }
+	}
+
+	/// 让时间向前跳跃 `millis` 毫秒，从下一个区块开始生效
+	pub fn advance(&self, millis: u64) {
+		self.offset.fetch_add(millis, Ordering::Relaxed);
+	}
+
+	/// 计算在 `parent` 之上构建的区块的时间戳
+	pub fn timestamp(
+		&self,
+		client: &FullClient,
+		parent: <Block as BlockT>::Hash,
+	) -> Result<sp_timestamp::InherentDataProvider, Box<dyn std::error::Error + Send + Sync>> {
+		let parent_timestamp = parent_timestamp(client, parent)?;
+		let timestamp = self.next(parent_timestamp, system_time());
+		Ok(sp_timestamp::InherentDataProvider::new(timestamp.into()))
+	}
+
+	/// 父区块的时间戳为 `parent_timestamp`（父区块为创世区块时为 `None`）、系统时间为 `now` 时，
+	/// 下一个区块的时间戳
+	fn next(&self, parent_timestamp: Option<u64>, now: u64) -> u64 {
+		let timestamp = match self.mode {
+			DevTimestampMode::System => now.saturating_add(self.offset.load(Ordering::Relaxed)),
+			DevTimestampMode::Fixed { start, step } =>
+				self.step_from(parent_timestamp, start, step),
+			DevTimestampMode::FollowSlot => {
+				let start = now / MILLI_SECS_PER_BLOCK * MILLI_SECS_PER_BLOCK;
+				self.step_from(parent_timestamp, start, MILLI_SECS_PER_BLOCK)
+			},
+		};
+		clamp_to_minimum_period(parent_timestamp, timestamp)
+	}
+
+	/// 父区块的时间戳加上 `step` 和尚未应用的跳跃时间；父区块为创世区块时使用 `start`
+	fn step_from(&self, parent_timestamp: Option<u64>, start: u64, step: u64) -> u64 {
+		let base = parent_timestamp.map_or(start, |parent| parent.saturating_add(step));
+		base.saturating_add(self.offset.swap(0, Ordering::Relaxed))
+	}
+}
+
+/// 使 `timestamp` 不早于父区块的时间戳加上运行时的 `MinimumPeriod`，否则时间戳 pallet 会拒绝该区块
+/// 出块间隔短于 `MinimumPeriod` 时（例如连续调用 `engine_createBlock`），时间戳会略微超前于系统时间
+pub fn clamp_to_minimum_period(parent_timestamp: Option<u64>, timestamp: u64) -> u64 {
+	match parent_timestamp {
+		Some(parent) => timestamp.max(parent.saturating_add(MinimumPeriod::get())),
+		None => timestamp,
+	}
+}
+
+/// 当前的系统时间（毫秒）
+fn system_time() -> u64 {
+	SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.expect("system time is after the unix epoch; qed")
+		.as_millis() as u64
+}
+
+/// 读取 `pallet_timestamp::Now`，创世区块中未设置时为 `None`
+pub fn parent_timestamp(
+	client: &FullClient,
+	parent: <Block as BlockT>::Hash,
+) -> Result<Option<u64>, Box<dyn std::error::Error + Send + Sync>> {
+	let key = StorageKey([twox_128(b"Timestamp"), twox_128(b"Now")].concat());
+	match client.storage(parent, &key)? {
+		Some(data) => Ok(Some(u64::decode(&mut &data.0[..])?)),
+		None => Ok(None),
+	}
+}
+
+/// 开发时钟 RPC
+#[rpc(server)]
+pub trait DevTimestampApi {
+	/// 让区块时间向前跳跃 `millis` 毫秒，从下一个区块开始生效
+	///
+	/// 属于不安全的 RPC，只有以 `--rpc-methods unsafe` 启动时才能调用
+	#[method(name = "dev_advanceTime", with_extensions)]
+	fn advance_time(&self, millis: u64) -> RpcResult<()>;
+}
+
+/// [`DevTimestampApiServer`] 的实现
+pub struct DevTimestamp {
+	clock: DevClock,
+}
+
+impl DevTimestamp {
+	/// 创建操作 `clock` 的 RPC 处理器
+	pub fn new(clock: DevClock) -> Self {
+		Self { clock }
+	}
+}
+
+impl DevTimestampApiServer for DevTimestamp {
+	fn advance_time(&self, ext: &Extensions, millis: u64) -> RpcResult<()> {
+		sc_rpc_api::check_if_safe(ext)?;
+		self.clock.advance(millis);
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const MIN: u64 = MinimumPeriod::get();
+
+	#[test]
+	fn parses_modes() {
+		assert_eq!("system".parse(), Ok(DevTimestampMode::System));
+		assert_eq!("follow-slot".parse(), Ok(DevTimestampMode::FollowSlot));
+		assert_eq!(
+			"fixed-start=0,step=3000".parse(),
+			Ok(DevTimestampMode::Fixed { start: 0, step: 3000 })
+		);
+		assert_eq!(
+			"fixed-step=6000,start=7".parse(),
+			Ok(DevTimestampMode::Fixed { start: 7, step: 6000 })
+		);
+	}
+
+	#[test]
+	fn rejects_invalid_modes() {
+		for mode in [
+			"slot",
+			"fixed-start=0",
+			"fixed-step=3000",
+			"fixed-start=0,step=abc",
+			"fixed-start=0,step=3000,end=1",
+			"fixed-start=0,step=0",
+		] {
+			assert!(mode.parse::<DevTimestampMode>().is_err(), "{mode}");
+		}
+	}
+
+	#[test]
+	fn rejects_step_below_minimum_period() {
+		let mode = format!("fixed-start=0,step={}", MIN - 1);
+		let err = mode.parse::<DevTimestampMode>().unwrap_err();
+		assert!(err.contains("MinimumPeriod"), "{err}");
+	}
+
+	#[test]
+	fn display_round_trips() {
+		for mode in [
+			DevTimestampMode::System,
+			DevTimestampMode::FollowSlot,
+			DevTimestampMode::Fixed { start: 5, step: 3000 },
+		] {
+			assert_eq!(mode.to_string().parse(), Ok(mode));
+		}
+	}
+
+	#[test]
+	fn fixed_mode_steps_from_start() {
+		let clock = DevClock::new(DevTimestampMode::Fixed { start: 0, step: 3000 });
+		assert_eq!(clock.next(None, 123), 0);
+		// 父区块的时间戳为 0 时不能被当成创世区块
+		assert_eq!(clock.next(Some(0), 123), 3000);
+		assert_eq!(clock.next(Some(3000), 123), 6000);
+	}
+
+	#[test]
+	fn follow_slot_aligns_to_slot_boundary() {
+		let clock = DevClock::new(DevTimestampMode::FollowSlot);
+		assert_eq!(clock.next(None, 7_500), 6_000);
+		assert_eq!(clock.next(Some(6_000), 7_500), 6_000 + MILLI_SECS_PER_BLOCK);
+	}
+
+	#[test]
+	fn system_mode_clamps_to_minimum_period() {
+		let clock = DevClock::new(DevTimestampMode::System);
+		assert_eq!(clock.next(None, 10_000), 10_000);
+		assert_eq!(clock.next(Some(10_000), 10_001), 10_000 + MIN);
+		assert_eq!(clock.next(Some(10_000), 20_000), 20_000);
+	}
+
+	#[test]
+	fn advance_accumulates_in_system_mode() {
+		let clock = DevClock::new(DevTimestampMode::System);
+		clock.advance(1_000);
+		clock.advance(2_000);
+		assert_eq!(clock.next(None, 10_000), 13_000);
+		// 跳跃的时间一直有效
+		assert_eq!(clock.next(Some(13_000), 20_000), 23_000);
+	}
+
+	#[test]
+	fn advance_applies_once_in_fixed_mode() {
+		let clock = DevClock::new(DevTimestampMode::Fixed { start: 0, step: 3000 });
+		clock.clone().advance(500);
+		assert_eq!(clock.next(Some(0), 0), 3_500);
+		assert_eq!(clock.next(Some(3_500), 0), 6_500);
+	}
+}
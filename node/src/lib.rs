@@ -19,6 +19,10 @@
 pub mod chain_spec;
 /// 命令行接口模块（内部使用）
 pub(crate) mod cli;
+/// 手动出块和即时出块的时间戳模块
+pub mod dev_timestamp;
+/// 工作量证明算法模块
+pub mod pow;
 /// RPC 接口模块
 pub mod rpc;
 /// 服务构建模块
@@ -19,11 +19,13 @@
 //! Substrate 节点模板 CLI 库
 #![warn(missing_docs)]
 
-mod chain_spec;  // 链规范配置模块
-mod cli;         // 命令行接口模块
-mod command;     // 命令处理模块
-mod rpc;         // RPC 接口模块
-mod service;     // 服务构建模块
+mod chain_spec;    // 链规范配置模块
+mod cli;           // 命令行接口模块
+mod command;       // 命令处理模块
+mod dev_timestamp; // 开发模式时间戳模块
+mod pow;           // 工作量证明算法模块
+mod rpc;           // RPC 接口模块
+mod service;       // 服务构建模块
 
 /// 程序入口点
 /// 解析命令行参数并执行相应的命令
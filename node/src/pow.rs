@@ -0,0 +1,196 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `--consensus pow` 模式使用的工作量证明算法
+//!
+//! 对 `(difficulty, pre_hash, nonce)` 的编码做 Blake2-256 哈希，哈希值乘以难度不溢出 `U256` 即为有效，
+//! 难度通过 `DifficultyApi` 从运行时的难度 pallet 读取
+
+use codec::{Decode, Encode};
+use minimal_template_runtime::interface::OpaqueBlock as Block;
+use polkadot_sdk::{
+	sc_consensus_pow::{Error, PowAlgorithm},
+	sp_api::ProvideRuntimeApi,
+	sp_consensus_pow::{DifficultyApi, Seal as RawSeal},
+	sp_core::{hashing::blake2_256, H256, U256},
+	sp_runtime::generic::BlockId,
+};
+use std::sync::Arc;
+
+/// `hash` 是否满足难度 `difficulty`：哈希值乘以难度不能溢出 `U256`
+pub fn hash_meets_difficulty(hash: &H256, difficulty: U256) -> bool {
+	let num_hash = U256::from_big_endian(hash.as_bytes());
+	!num_hash.overflowing_mul(difficulty).1
+}
+
+/// 写入区块头的工作量证明
+#[derive(Clone, PartialEq, Eq, Encode, Decode, Debug)]
+pub struct Seal {
+	/// 挖矿时使用的难度
+	pub difficulty: U256,
+	/// 满足难度的哈希值
+	pub work: H256,
+	/// 得到该哈希值的 nonce
+	pub nonce: U256,
+}
+
+/// 计算工作量证明时被哈希的内容
+#[derive(Clone, PartialEq, Eq, Encode, Decode, Debug)]
+pub struct Compute {
+	/// 当前的难度
+	pub difficulty: U256,
+	/// 不含工作量证明的区块头哈希
+	pub pre_hash: H256,
+	/// 尝试的 nonce
+	pub nonce: U256,
+}
+
+impl Compute {
+	/// 计算哈希值并生成对应的 [`Seal`]，不检查是否满足难度
+	pub fn compute(self) -> Seal {
+		let work = H256::from(blake2_256(&self.encode()));
+		Seal { difficulty: self.difficulty, work, nonce: self.nonce }
+	}
+}
+
+/// 从 `nonce` 开始，以 `step` 为步长尝试 `rounds` 个 nonce，返回第一个满足难度的工作量证明
+/// 返回时 `nonce` 指向下一个待尝试的值，多个线程以不同的起点和相同的步长划分 nonce 空间
+pub fn mine(
+	pre_hash: H256,
+	difficulty: U256,
+	nonce: &mut U256,
+	step: U256,
+	rounds: usize,
+) -> Option<Seal> {
+	for _ in 0..rounds {
+		let seal = Compute { difficulty, pre_hash, nonce: *nonce }.compute();
+		*nonce = nonce.overflowing_add(step).0;
+		if hash_meets_difficulty(&seal.work, difficulty) {
+			return Some(seal);
+		}
+	}
+	None
+}
+
+/// 基于 Blake2-256 的工作量证明算法
+pub struct Blake2Algorithm<C> {
+	client: Arc<C>,
+}
+
+impl<C> Blake2Algorithm<C> {
+	/// 创建算法实例，难度从 `client` 的运行时读取
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client }
+	}
+}
+
+// 手动实现，避免要求 `C: Clone`
+impl<C> Clone for Blake2Algorithm<C> {
+	fn clone(&self) -> Self {
+		Self { client: self.client.clone() }
+	}
+}
+
+impl<C> PowAlgorithm<Block> for Blake2Algorithm<C>
+where
+	C: ProvideRuntimeApi<Block>,
+	C::Api: DifficultyApi<Block, U256>,
+{
+	type Difficulty = U256;
+
+	fn difficulty(&self, parent: H256) -> Result<U256, Error<Block>> {
+		self.client.runtime_api().difficulty(parent).map_err(|err| {
+			Error::Environment(format!("Fetching difficulty from runtime failed: {:?}", err))
+		})
+	}
+
+	fn verify(
+		&self,
+		_parent: &BlockId<Block>,
+		pre_hash: &H256,
+		_pre_digest: Option<&[u8]>,
+		seal: &RawSeal,
+		difficulty: U256,
+	) -> Result<bool, Error<Block>> {
+		Ok(verify_seal(pre_hash, seal, difficulty))
+	}
+}
+
+/// 检查 `seal` 是否为 `pre_hash` 在难度 `difficulty` 下的有效工作量证明
+pub fn verify_seal(pre_hash: &H256, seal: &RawSeal, difficulty: U256) -> bool {
+	let Ok(seal) = Seal::decode(&mut &seal[..]) else { return false };
+	if !hash_meets_difficulty(&seal.work, difficulty) {
+		return false;
+	}
+
+	// 重新计算哈希，确认 seal 中的难度和哈希值都与区块一致
+	let compute = Compute { difficulty, pre_hash: *pre_hash, nonce: seal.nonce };
+	compute.compute() == seal
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const DIFFICULTY: u64 = 16;
+
+	/// 以单线程挖出 `pre_hash` 的工作量证明
+	fn mine_seal(pre_hash: H256) -> Seal {
+		let mut nonce = U256::zero();
+		mine(pre_hash, DIFFICULTY.into(), &mut nonce, U256::one(), 100_000)
+			.expect("difficulty is low enough to find a seal; qed")
+	}
+
+	#[test]
+	fn hash_meets_difficulty_works() {
+		assert!(hash_meets_difficulty(&H256::zero(), U256::MAX));
+		assert!(hash_meets_difficulty(&H256::repeat_byte(0xff), U256::one()));
+		assert!(!hash_meets_difficulty(&H256::repeat_byte(0xff), U256::from(2)));
+	}
+
+	#[test]
+	fn mined_seal_verifies() {
+		let pre_hash = H256::repeat_byte(1);
+		let seal = mine_seal(pre_hash);
+
+		assert!(hash_meets_difficulty(&seal.work, DIFFICULTY.into()));
+		assert!(verify_seal(&pre_hash, &seal.encode(), DIFFICULTY.into()));
+	}
+
+	#[test]
+	fn mine_advances_nonce_by_step() {
+		let mut nonce = U256::from(3);
+		assert_eq!(mine(H256::zero(), U256::MAX, &mut nonce, U256::from(4), 2), None);
+		assert_eq!(nonce, U256::from(11));
+	}
+
+	#[test]
+	fn rejects_bad_seal() {
+		let pre_hash = H256::repeat_byte(1);
+		let seal = mine_seal(pre_hash);
+
+		// 其他区块头
+		assert!(!verify_seal(&H256::repeat_byte(2), &seal.encode(), DIFFICULTY.into()));
+		// 与 seal 中记录的难度不一致
+		assert!(!verify_seal(&pre_hash, &seal.encode(), (DIFFICULTY - 1).into()));
+		// 篡改 nonce 后哈希值对不上
+		let tampered = Seal { nonce: seal.nonce + 1, ..seal.clone() };
+		assert!(!verify_seal(&pre_hash, &tampered.encode(), DIFFICULTY.into()));
+		// 无法解码
+		assert!(!verify_seal(&pre_hash, &vec![0u8; 3], DIFFICULTY.into()));
+	}
+}
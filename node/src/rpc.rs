@@ -21,29 +21,74 @@
 
 #![warn(missing_docs)]
 
+use crate::dev_timestamp::DevClock;
+use futures::channel::mpsc;
 use jsonrpsee::RpcModule;
-use minimal_template_runtime::interface::{AccountId, Nonce, OpaqueBlock};
+use minimal_template_runtime::interface::{AccountId, Balance, Nonce, OpaqueBlock};
 use polkadot_sdk::{
+	sc_consensus_grandpa::{
+		FinalityProofProvider, GrandpaJustificationStream, SharedAuthoritySet, SharedVoterState,
+	},
+	sc_consensus_babe::BabeWorkerHandle,
+	sc_consensus_manual_seal::EngineCommand,
+	sc_rpc::SubscriptionTaskExecutor,
 	sc_transaction_pool_api::TransactionPool,
 	sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata},
+	sp_consensus::SelectChain,
+	sp_keystore::KeystorePtr,
+	sp_runtime::traits::{Block as BlockT, NumberFor},
 	*,
 };
 use std::sync::Arc;
 
+/// GRANDPA RPC 所需的依赖项
+pub struct GrandpaDeps<B> {
+	/// 投票者状态
+	pub shared_voter_state: SharedVoterState,
+	/// 当前的投票者集合
+	pub shared_authority_set:
+		SharedAuthoritySet<<OpaqueBlock as BlockT>::Hash, NumberFor<OpaqueBlock>>,
+	/// 最终性证明（justification）订阅流
+	pub justification_stream: GrandpaJustificationStream<OpaqueBlock>,
+	/// 订阅任务执行器
+	pub subscription_executor: SubscriptionTaskExecutor,
+	/// 最终性证明提供者
+	pub finality_provider: Arc<FinalityProofProvider<B, OpaqueBlock>>,
+}
+
+/// BABE RPC 所需的依赖项
+#[derive(Clone)]
+pub struct BabeDeps<SC> {
+	/// BABE 工作线程句柄，用于查询当前纪元
+	pub worker_handle: BabeWorkerHandle<OpaqueBlock>,
+	/// 密钥库，用于判断本节点在当前纪元中拥有哪些 slot
+	pub keystore: KeystorePtr,
+	/// 链选择器
+	pub select_chain: SC,
+}
+
 /// 完整客户端依赖项
 /// 包含 RPC 服务所需的所有依赖
-pub struct FullDeps<C, P> {
+pub struct FullDeps<C, P, B, SC> {
 	/// 要使用的客户端实例
 	pub client: Arc<C>,
 	/// 交易池实例
 	pub pool: Arc<P>,
+	/// GRANDPA 依赖项，仅在 `--finality grandpa` 模式下存在
+	pub grandpa: Option<GrandpaDeps<B>>,
+	/// BABE 依赖项，仅在 `--consensus babe` 模式下存在
+	pub babe: Option<BabeDeps<SC>>,
+	/// 手动出块的命令通道，仅在 `--consensus manual-seal` 模式下存在
+	pub command_sink: Option<mpsc::Sender<EngineCommand<<OpaqueBlock as BlockT>::Hash>>>,
+	/// 开发时钟，仅在手动出块和即时出块模式下存在
+	pub dev_clock: Option<DevClock>,
 }
 
 #[docify::export]
 /// 实例化所有完整的 RPC 扩展
 /// 创建并配置所有 RPC 方法模块
-pub fn create_full<C, P>(
-	deps: FullDeps<C, P>,
+pub fn create_full<C, P, B, SC>(
+	deps: FullDeps<C, P, B, SC>,
 ) -> Result<RpcModule<()>, Box<dyn std::error::Error + Send + Sync>>
 where
 	C: Send
@@ -55,14 +100,63 @@ where
 		+ 'static,
 	C::Api: sp_block_builder::BlockBuilder<OpaqueBlock>,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<OpaqueBlock, AccountId, Nonce>,
+	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<OpaqueBlock, Balance>,
+	C::Api: sp_consensus_babe::BabeApi<OpaqueBlock>,
 	P: TransactionPool + 'static,
+	B: sc_client_api::Backend<OpaqueBlock> + Send + Sync + 'static,
+	SC: SelectChain<OpaqueBlock> + 'static,
 {
-	use polkadot_sdk::substrate_frame_rpc_system::{System, SystemApiServer};
+	use crate::dev_timestamp::{DevTimestamp, DevTimestampApiServer};
+	use polkadot_sdk::{
+		pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer},
+		sc_consensus_babe_rpc::{Babe, BabeApiServer},
+		sc_consensus_grandpa_rpc::{Grandpa, GrandpaApiServer},
+		sc_consensus_manual_seal::rpc::{ManualSeal, ManualSealApiServer},
+		substrate_frame_rpc_system::{System, SystemApiServer},
+	};
 	let mut module = RpcModule::new(());
-	let FullDeps { client, pool } = deps;
+	let FullDeps { client, pool, grandpa, babe, command_sink, dev_clock } = deps;
 
 	// 添加系统 RPC 方法（账户 nonce 等）
 	module.merge(System::new(client.clone(), pool.clone()).into_rpc())?;
+	// 添加交易支付 RPC 方法（`payment_queryInfo`、`payment_queryFeeDetails`）
+	module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+
+	// 添加 BABE RPC 方法（`babe_epochAuthorship`）
+	if let Some(BabeDeps { worker_handle, keystore, select_chain }) = babe {
+		module.merge(Babe::new(client.clone(), worker_handle, keystore, select_chain).into_rpc())?;
+	}
+
+	// 添加手动出块 RPC 方法（`engine_createBlock`、`engine_finalizeBlock`）
+	if let Some(command_sink) = command_sink {
+		module.merge(ManualSeal::new(command_sink).into_rpc())?;
+	}
+
+	// 添加开发时钟 RPC 方法（`dev_advanceTime`）
+	if let Some(dev_clock) = dev_clock {
+		module.merge(DevTimestamp::new(dev_clock).into_rpc())?;
+	}
+
+	// 添加 GRANDPA RPC 方法（`grandpa_roundState`、`grandpa_proveFinality` 等）
+	if let Some(GrandpaDeps {
+		shared_voter_state,
+		shared_authority_set,
+		justification_stream,
+		subscription_executor,
+		finality_provider,
+	}) = grandpa
+	{
+		module.merge(
+			Grandpa::new(
+				subscription_executor,
+				shared_authority_set,
+				shared_voter_state,
+				justification_stream,
+				finality_provider,
+			)
+			.into_rpc(),
+		)?;
+	}
 
 	Ok(module)
 }
@@ -15,22 +15,39 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::cli::Consensus;
-use futures::FutureExt;
+use crate::{
+	cli::{Consensus, Finality, FinalityDelay, SealDecision, SealParams},
+	dev_timestamp::{self, DevClock, DevTimestampMode},
+	pow::{self, Blake2Algorithm},
+};
+use codec::Encode;
+use futures::{FutureExt, StreamExt};
 use minimal_template_runtime::{interface::OpaqueBlock as Block, RuntimeApi};
 use polkadot_sdk::{
-	sc_client_api::backend::Backend,
+	sc_client_api::{backend::Backend, BlockchainEvents, Finalizer},
 	sc_executor::WasmExecutor,
 	sc_service::{error::Error as ServiceError, Configuration, TaskManager},
 	sc_telemetry::{Telemetry, TelemetryWorker},
-	sc_transaction_pool_api::OffchainTransactionPoolFactory,
-	sp_runtime::traits::Block as BlockT,
+	sc_transaction_pool_api::{OffchainTransactionPoolFactory, TransactionPool},
+	sp_consensus_aura::sr25519::AuthorityPair as AuraPair,
+	sp_core::U256,
+	sp_blockchain::HeaderBackend,
+	sp_runtime::traits::{Block as BlockT, Header as HeaderT},
+	substrate_prometheus_endpoint::{register, Counter, PrometheusError, Registry, U64},
 	*,
 };
-use std::sync::Arc;
+use std::{
+	sync::Arc,
+	time::{Duration, Instant},
+};
 
 /// Wasm 执行器的宿主函数类型
+#[cfg(not(feature = "runtime-benchmarks"))]
 type HostFunctions = sp_io::SubstrateHostFunctions;
+/// Wasm 执行器的宿主函数类型，额外包含基准测试所需的宿主函数
+#[cfg(feature = "runtime-benchmarks")]
+type HostFunctions =
+	(sp_io::SubstrateHostFunctions, frame_benchmarking::benchmarking::HostFunctions);
 
 /// 完整客户端类型
 /// 包含区块、运行时 API 和 Wasm 执行器
@@ -42,6 +59,103 @@ pub(crate) type FullClient =
 type FullBackend = sc_service::TFullBackend<Block>;
 /// 最长链选择器类型
 type FullSelectChain = sc_consensus::LongestChain<FullBackend, Block>;
+/// 包装了客户端的 GRANDPA 区块导入，跟踪投票者集合的变更
+type GrandpaBlockImport =
+	sc_consensus_grandpa::GrandpaBlockImport<FullBackend, Block, FullClient, FullSelectChain>;
+/// GRANDPA 区块导入与投票者之间共享的状态
+type GrandpaLinkHalf = sc_consensus_grandpa::LinkHalf<Block, FullClient, FullSelectChain>;
+
+/// BABE 区块导入校验 inherent 时使用的数据提供者
+type BabeCreateInherentDataProviders = Arc<
+	dyn sp_inherents::CreateInherentDataProviders<
+			Block,
+			(),
+			InherentDataProviders = (
+				sp_consensus_babe::inherents::InherentDataProvider,
+				sp_timestamp::InherentDataProvider,
+			),
+		> + Send
+		+ Sync,
+>;
+/// 包装了 GRANDPA 区块导入的 BABE 区块导入，校验 VRF 证明并跟踪纪元变更
+type BabeBlockImport = sc_consensus_babe::BabeBlockImport<
+	Block,
+	FullClient,
+	GrandpaBlockImport,
+	BabeCreateInherentDataProviders,
+	FullSelectChain,
+>;
+
+/// `--consensus babe` 模式下出块和 RPC 需要的 BABE 组件
+pub struct BabeParts {
+	/// BABE 区块导入
+	pub block_import: BabeBlockImport,
+	/// 区块导入与出块任务之间共享的纪元状态
+	pub link: sc_consensus_babe::BabeLink<Block>,
+	/// BABE 工作线程句柄，供 `babe_epochAuthorship` RPC 查询纪元
+	pub worker_handle: sc_consensus_babe::BabeWorkerHandle<Block>,
+}
+
+/// 每隔多少个区块生成一次 GRANDPA justification，供同步节点校验最终性
+const GRANDPA_JUSTIFICATION_PERIOD: u32 = 512;
+
+/// 日志目标
+const LOG_TARGET: &str = "manual-seal";
+
+/// 手动出块定时器的 Prometheus 指标
+struct ManualSealMetrics {
+	/// 因命令队列已满而跳过的出块次数
+	skipped_seals: Counter<U64>,
+}
+
+impl ManualSealMetrics {
+	fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		Ok(Self {
+			skipped_seals: register(
+				Counter::new(
+					"manual_seal_skipped_ticks_total",
+					"Number of manual-seal timer ticks skipped because the command queue was full",
+				)?,
+				registry,
+			)?,
+		})
+	}
+}
+
+/// 每个挖矿线程在检查新的待挖区块之前连续尝试的 nonce 数量
+const POW_NONCES_PER_ROUND: usize = 10_000;
+
+/// 创建 `--consensus pow` 模式的区块导入
+/// 包装 GRANDPA 区块导入，校验工作量证明，并按累计难度选择最佳链
+fn pow_block_import(
+	client: Arc<FullClient>,
+	grandpa_block_import: GrandpaBlockImport,
+	select_chain: FullSelectChain,
+) -> sc_consensus::BoxBlockImport<Block> {
+	Box::new(sc_consensus_pow::PowBlockImport::new(
+		grandpa_block_import,
+		client.clone(),
+		Blake2Algorithm::new(client),
+		0, // 从创世区块开始校验 inherent
+		select_chain,
+		move |_, ()| async move { Ok(sp_timestamp::InherentDataProvider::from_system_time()) },
+	))
+}
+
+/// 手动出块和即时出块使用的 inherent 数据提供者，时间戳由 `clock` 决定
+fn dev_inherent_data_providers(
+	client: Arc<FullClient>,
+	clock: DevClock,
+) -> impl sp_inherents::CreateInherentDataProviders<
+	Block,
+	(),
+	InherentDataProviders = sp_timestamp::InherentDataProvider,
+> {
+	move |parent, ()| {
+		let timestamp = clock.timestamp(&client, parent);
+		async move { timestamp }
+	}
+}
 
 /// 部分组件组装
 /// 包含运行链操作子命令所需的足够组件（不需要完整节点）
@@ -51,12 +165,13 @@ pub type Service = sc_service::PartialComponents<
 	FullSelectChain,                               // 链选择器
 	sc_consensus::DefaultImportQueue<Block>,       // 导入队列
 	sc_transaction_pool::TransactionPoolHandle<Block, FullClient>, // 交易池句柄
-	Option<Telemetry>,                             // 遥测（可选）
+	// GRANDPA 导入和链接、BABE 组件（仅 BABE 模式）以及遥测
+	(GrandpaBlockImport, GrandpaLinkHalf, Option<BabeParts>, Option<Telemetry>),
 >;
 
 /// 创建部分服务组件
 /// 用于运行链操作子命令（如导入区块、导出状态等）
-pub fn new_partial(config: &Configuration) -> Result<Service, ServiceError> {
+pub fn new_partial(config: &Configuration, consensus: &Consensus) -> Result<Service, ServiceError> {
 	// 初始化遥测（如果配置了遥测端点）
 	let telemetry = config
 		.telemetry_endpoints
@@ -90,6 +205,15 @@ pub fn new_partial(config: &Configuration) -> Result<Service, ServiceError> {
 	// 创建最长链选择器
 	let select_chain = sc_consensus::LongestChain::new(backend.clone());
 
+	// 所有模式都通过 GRANDPA 区块导入导入区块，只有 `--finality grandpa` 才会启动投票者
+	let (grandpa_block_import, grandpa_link) = sc_consensus_grandpa::block_import(
+		client.clone(),
+		GRANDPA_JUSTIFICATION_PERIOD,
+		&client,
+		select_chain.clone(),
+		telemetry.as_ref().map(|x| x.handle()),
+	)?;
+
 	// 创建交易池
 	let transaction_pool = Arc::from(
 		sc_transaction_pool::Builder::new(
@@ -102,12 +226,89 @@ pub fn new_partial(config: &Configuration) -> Result<Service, ServiceError> {
 		.build(),
 	);
 
-	// 创建手动出块的导入队列
-	let import_queue = sc_consensus_manual_seal::import_queue(
-		Box::new(client.clone()),
-		&task_manager.spawn_essential_handle(),
-		config.prometheus_registry(),
-	);
+	let (import_queue, babe) = match consensus {
+		// Aura 模式下导入的区块需要校验 slot 摘要和出块者签名
+		Consensus::Aura => {
+			let slot_duration = sc_consensus_aura::slot_duration(&*client)?;
+			let import_queue = sc_consensus_aura::import_queue::<AuraPair, _, _, _, _, _>(
+				sc_consensus_aura::ImportQueueParams {
+					block_import: grandpa_block_import.clone(),
+					justification_import: Some(Box::new(grandpa_block_import.clone())),
+					client: client.clone(),
+					create_inherent_data_providers: move |_, ()| async move {
+						let timestamp = sp_timestamp::InherentDataProvider::from_system_time();
+						let slot =
+							sp_consensus_aura::inherents::InherentDataProvider::from_timestamp_and_slot_duration(
+								*timestamp,
+								slot_duration,
+							);
+						Ok((slot, timestamp))
+					},
+					spawner: &task_manager.spawn_essential_handle(),
+					registry: config.prometheus_registry(),
+					check_for_equivocation: Default::default(),
+					telemetry: telemetry.as_ref().map(|x| x.handle()),
+					compatibility_mode: Default::default(),
+				},
+			)?;
+			(import_queue, None)
+		},
+		// BABE 模式下导入的区块需要校验 VRF 证明，并在导入时跟踪纪元变更
+		Consensus::Babe => {
+			let babe_config = sc_consensus_babe::configuration(&*client)?;
+			let slot_duration = babe_config.slot_duration();
+			let create_inherent_data_providers: BabeCreateInherentDataProviders =
+				Arc::new(move |_, ()| async move {
+					let timestamp = sp_timestamp::InherentDataProvider::from_system_time();
+					let slot =
+						sp_consensus_babe::inherents::InherentDataProvider::from_timestamp_and_slot_duration(
+							*timestamp,
+							slot_duration,
+						);
+					Ok((slot, timestamp))
+				});
+			let (block_import, link) = sc_consensus_babe::block_import(
+				babe_config,
+				grandpa_block_import.clone(),
+				client.clone(),
+				create_inherent_data_providers,
+				select_chain.clone(),
+				OffchainTransactionPoolFactory::new(transaction_pool.clone()),
+			)?;
+			let (import_queue, worker_handle) =
+				sc_consensus_babe::import_queue(sc_consensus_babe::ImportQueueParams {
+					link: link.clone(),
+					block_import: block_import.clone(),
+					justification_import: Some(Box::new(grandpa_block_import.clone())),
+					client: client.clone(),
+					slot_duration,
+					spawner: &task_manager.spawn_essential_handle(),
+					registry: config.prometheus_registry(),
+					telemetry: telemetry.as_ref().map(|x| x.handle()),
+				})?;
+			(import_queue, Some(BabeParts { block_import, link, worker_handle }))
+		},
+		// 工作量证明模式下导入的区块需要校验 seal 是否满足链上难度
+		Consensus::Pow => (
+			sc_consensus_pow::import_queue(
+				pow_block_import(client.clone(), grandpa_block_import.clone(), select_chain.clone()),
+				Some(Box::new(grandpa_block_import.clone())),
+				Blake2Algorithm::new(client.clone()),
+				&task_manager.spawn_essential_handle(),
+				config.prometheus_registry(),
+			)?,
+			None,
+		),
+		// 其他模式使用手动出块的导入队列，不做共识校验
+		_ => (
+			sc_consensus_manual_seal::import_queue(
+				Box::new(grandpa_block_import.clone()),
+				&task_manager.spawn_essential_handle(),
+				config.prometheus_registry(),
+			),
+			None,
+		),
+	};
 
 	Ok(sc_service::PartialComponents {
 		client,
@@ -117,7 +318,7 @@ pub fn new_partial(config: &Configuration) -> Result<Service, ServiceError> {
 		keystore_container,
 		select_chain,
 		transaction_pool,
-		other: (telemetry),
+		other: (grandpa_block_import, grandpa_link, babe, telemetry),
 	})
 }
 
@@ -126,7 +327,33 @@ pub fn new_partial(config: &Configuration) -> Result<Service, ServiceError> {
 pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Hash>>(
 	config: Configuration,
 	consensus: Consensus,
+	finality: Finality,
+	finality_delay: Option<FinalityDelay>,
+	dev_timestamp: DevTimestampMode,
+	seal: SealParams,
+	pow_threads: usize,
 ) -> Result<TaskManager, ServiceError> {
+	seal.validate(&consensus).map_err(ServiceError::Other)?;
+	if dev_timestamp != DevTimestampMode::System &&
+		!matches!(consensus, Consensus::ManualSeal(_) | Consensus::InstantSeal)
+	{
+		return Err(ServiceError::Other(
+			"`--dev-timestamp` is only supported with manual seal and instant seal".into(),
+		));
+	}
+	if finality_delay.is_some() {
+		if finality == Finality::Grandpa {
+			return Err(ServiceError::Other(
+				"`--finality-delay` cannot be combined with `--finality grandpa`".into(),
+			));
+		}
+		if !matches!(consensus, Consensus::ManualSeal(_) | Consensus::InstantSeal) {
+			return Err(ServiceError::Other(
+				"`--finality-delay` is only supported with manual seal and instant seal".into(),
+			));
+		}
+	}
+
 	let sc_service::PartialComponents {
 		client,
 		backend,
@@ -135,10 +362,10 @@ pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Ha
 		keystore_container,
 		select_chain,
 		transaction_pool,
-		other: mut telemetry,
-	} = new_partial(&config)?;
+		other: (block_import, grandpa_link, babe, mut telemetry),
+	} = new_partial(&config, &consensus)?;
 
-	let net_config = sc_network::config::FullNetworkConfiguration::<
+	let mut net_config = sc_network::config::FullNetworkConfiguration::<
 		Block,
 		<Block as BlockT>::Hash,
 		Network,
@@ -150,6 +377,34 @@ pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Ha
 		config.prometheus_config.as_ref().map(|cfg| &cfg.registry),
 	);
 
+	// 启用 GRANDPA 时注册其 gossip 协议，并通过 warp sync 快速同步到最新的最终化区块
+	let grandpa_protocol_name = sc_consensus_grandpa::protocol_standard_name(
+		&client.block_hash(0).ok().flatten().expect("Genesis block exists; qed"),
+		&config.chain_spec,
+	);
+	let (grandpa_notification_service, warp_sync_config) = match finality {
+		Finality::Grandpa => {
+			let (grandpa_protocol_config, notification_service) =
+				sc_consensus_grandpa::grandpa_peers_set_config::<_, Network>(
+					grandpa_protocol_name.clone(),
+					metrics.clone(),
+					net_config.peer_store_handle(),
+				);
+			net_config.add_notification_protocol(grandpa_protocol_config);
+
+			let warp_sync = Arc::new(sc_consensus_grandpa::warp_proof::NetworkProvider::new(
+				backend.clone(),
+				grandpa_link.shared_authority_set().clone(),
+				Vec::default(),
+			));
+			(
+				Some(notification_service),
+				Some(sc_service::WarpSyncConfig::WithProvider(warp_sync)),
+			)
+		},
+		Finality::None => (None, None),
+	};
+
 	let (network, system_rpc_tx, tx_handler_controller, sync_service) =
 		sc_service::build_network(sc_service::BuildNetworkParams {
 			config: &config,
@@ -160,7 +415,7 @@ pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Ha
 			spawn_essential_handle: task_manager.spawn_essential_handle(),
 			import_queue,
 			block_announce_validator_builder: None,
-			warp_sync_config: None,
+			warp_sync_config,
 			block_relay: None,
 			metrics,
 		})?;
@@ -186,20 +441,76 @@ pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Ha
 		);
 	}
 
+	let shared_voter_state = sc_consensus_grandpa::SharedVoterState::empty();
+
+	// 手动出块的命令通道：定时器和 `engine_*` RPC 共用同一个发送端
+	let (command_sink, commands_stream) = match consensus {
+		Consensus::ManualSeal(_) => {
+			let (sink, stream) = futures::channel::mpsc::channel(1024);
+			(Some(sink), Some(stream))
+		},
+		_ => (None, None),
+	};
+
+	// 手动出块和即时出块模式下由开发时钟决定区块时间戳，其他模式的时间戳受 slot 或共识校验约束
+	let dev_clock = matches!(consensus, Consensus::ManualSeal(_) | Consensus::InstantSeal)
+		.then(|| DevClock::new(dev_timestamp));
+
 	let rpc_extensions_builder = {
 		let client = client.clone();
 		let pool = transaction_pool.clone();
+		let grandpa_rpc = (finality == Finality::Grandpa).then(|| {
+			(
+				shared_voter_state.clone(),
+				grandpa_link.shared_authority_set().clone(),
+				grandpa_link.justification_stream(),
+				sc_consensus_grandpa::FinalityProofProvider::new_for_service(
+					backend.clone(),
+					Some(grandpa_link.shared_authority_set().clone()),
+				),
+			)
+		});
+		let command_sink = command_sink.clone();
+		let dev_clock = dev_clock.clone();
+		let babe_rpc = babe.as_ref().map(|babe| crate::rpc::BabeDeps {
+			worker_handle: babe.worker_handle.clone(),
+			keystore: keystore_container.keystore(),
+			select_chain: select_chain.clone(),
+		});
 
-		Box::new(move |_| {
-			let deps = crate::rpc::FullDeps { client: client.clone(), pool: pool.clone() };
+		Box::new(move |subscription_executor| {
+			let grandpa = grandpa_rpc.clone().map(
+				|(shared_voter_state, shared_authority_set, justification_stream, finality_provider)| {
+					crate::rpc::GrandpaDeps {
+						shared_voter_state,
+						shared_authority_set,
+						justification_stream,
+						subscription_executor,
+						finality_provider,
+					}
+				},
+			);
+			let deps = crate::rpc::FullDeps {
+				client: client.clone(),
+				pool: pool.clone(),
+				grandpa,
+				babe: babe_rpc.clone(),
+				command_sink: command_sink.clone(),
+				dev_clock: dev_clock.clone(),
+			};
 			crate::rpc::create_full(deps).map_err(Into::into)
 		})
 	};
 
 	let prometheus_registry = config.prometheus_registry().cloned();
+	// `config` 在启动 RPC 等任务时会被移走，提前取出出块需要的配置
+	let role = config.role;
+	let is_authority = role.is_authority();
+	let force_authoring = config.force_authoring;
+	let name = config.network.node_name.clone();
 
 	let _rpc_handlers = sc_service::spawn_tasks(sc_service::SpawnTasksParams {
-		network,
+		network: network.clone(),
 		client: client.clone(),
 		keystore: keystore_container.keystore(),
 		task_manager: &mut task_manager,
@@ -208,7 +519,7 @@ pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Ha
 		backend,
 		system_rpc_tx,
 		tx_handler_controller,
-		sync_service,
+		sync_service: sync_service.clone(),
 		config,
 		telemetry: telemetry.as_mut(),
 		tracing_execute_block: None,
@@ -222,20 +533,118 @@ pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Ha
 		telemetry.as_ref().map(|x| x.handle()),
 	);
 
+	// 启动 GRANDPA 投票者；非验证人节点不持有密钥，只跟踪投票
+	if let Some(notification_service) = grandpa_notification_service {
+		let grandpa_config = sc_consensus_grandpa::Config {
+			gossip_duration: Duration::from_millis(333),
+			justification_generation_period: GRANDPA_JUSTIFICATION_PERIOD,
+			name: Some(name),
+			observer_enabled: false,
+			keystore: is_authority.then(|| keystore_container.keystore()),
+			local_role: role,
+			telemetry: telemetry.as_ref().map(|x| x.handle()),
+			protocol_name: grandpa_protocol_name,
+		};
+
+		let grandpa_params = sc_consensus_grandpa::GrandpaParams {
+			config: grandpa_config,
+			link: grandpa_link,
+			network,
+			sync: Arc::new(sync_service.clone()),
+			notification_service,
+			voting_rule: sc_consensus_grandpa::VotingRulesBuilder::default().build(),
+			prometheus_registry: prometheus_registry.clone(),
+			shared_voter_state,
+			telemetry: telemetry.as_ref().map(|x| x.handle()),
+			offchain_tx_pool_factory: OffchainTransactionPoolFactory::new(
+				transaction_pool.clone(),
+			),
+		};
+
+		task_manager.spawn_essential_handle().spawn_blocking(
+			"grandpa-voter",
+			None,
+			sc_consensus_grandpa::run_grandpa_voter(grandpa_params)?,
+		);
+	}
+
+	// 延迟最终化：区块先作为最佳区块存在一段时间，再被最终化
+	match finality_delay {
+		Some(FinalityDelay::Millis(delay)) if delay % 1000 == 0 => {
+			let params = sc_consensus_manual_seal::DelayedFinalizeParams {
+				client: client.clone(),
+				spawn_handle: task_manager.spawn_handle(),
+				delay_sec: delay / 1000,
+			};
+			task_manager.spawn_handle().spawn(
+				"delayed-finalize",
+				None,
+				sc_consensus_manual_seal::run_delayed_finalize(params),
+			);
+		},
+		// `run_delayed_finalize` 只接受整秒的 `delay_sec`，亚秒级的延迟在这里自行计时
+		Some(FinalityDelay::Millis(delay)) => {
+			let client = client.clone();
+			let spawn_handle = task_manager.spawn_handle();
+			task_manager.spawn_handle().spawn("delayed-finalize", None, async move {
+				let mut imports = client.import_notification_stream();
+				while let Some(notification) = imports.next().await {
+					// 每个区块单独计时，等待期间不影响后续区块的导入
+					let client = client.clone();
+					spawn_handle.spawn("delayed-finalize-block", None, async move {
+						futures_timer::Delay::new(Duration::from_millis(delay)).await;
+						if let Err(err) = client.finalize_block(notification.hash, None, true) {
+							log::warn!(
+								target: LOG_TARGET,
+								"Failed to finalize block {}: {err}",
+								notification.hash,
+							);
+						}
+					});
+				}
+			});
+		},
+		// 上游没有按区块深度最终化的实现
+		Some(FinalityDelay::Blocks(depth)) => {
+			let client = client.clone();
+			task_manager.spawn_handle().spawn("delayed-finalize", None, async move {
+				let mut imports = client.import_notification_stream();
+				while let Some(notification) = imports.next().await {
+					if !notification.is_new_best {
+						continue;
+					}
+					// 最终化最佳链上比新区块低 `depth` 个高度的区块
+					let Some(target) = notification.header.number().checked_sub(depth) else {
+						continue;
+					};
+					let result = client.hash(target).and_then(|hash| match hash {
+						Some(hash) => client.finalize_block(hash, None, true),
+						None => Ok(()),
+					});
+					if let Err(err) = result {
+						log::warn!(target: LOG_TARGET, "Failed to finalize block #{target}: {err}");
+					}
+				}
+			});
+		},
+		None => {},
+	}
+
 	// 根据共识类型启动相应的出块机制
 	match consensus {
-		/// 即时出块：收到交易立即出块
+		// 即时出块：收到交易立即出块
 		Consensus::InstantSeal => {
 			let params = sc_consensus_manual_seal::InstantSealParams {
-				block_import: client.clone(),
+				block_import,
 				env: proposer,
-				client,
+				client: client.clone(),
 				pool: transaction_pool,
 				select_chain,
 				consensus_data_provider: None,
-				create_inherent_data_providers: move |_, ()| async move {
-					Ok(sp_timestamp::InherentDataProvider::from_system_time())
-				},
+				create_inherent_data_providers: dev_inherent_data_providers(
+					client.clone(),
+					dev_clock.expect("dev clock is created for dev consensus modes; qed"),
+				),
 			};
 
 			let authorship_future = sc_consensus_manual_seal::run_instant_seal(params);
@@ -246,37 +655,79 @@ pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Ha
 				authorship_future,
 			);
 		},
-		/// 手动出块：按指定时间间隔出块
+		// 手动出块：按指定时间间隔出块，也可以通过 `engine_createBlock` RPC 随时出块
 		Consensus::ManualSeal(block_time) => {
-			// 创建命令通道，用于控制出块
-			let (mut sink, commands_stream) = futures::channel::mpsc::channel(1024);
-			// 启动定时出块任务
-			task_manager.spawn_handle().spawn("block_authoring", None, async move {
-				loop {
-					// 等待指定的时间间隔
-					futures_timer::Delay::new(std::time::Duration::from_millis(block_time)).await;
-					// 发送出块命令
-					sink.try_send(sc_consensus_manual_seal::EngineCommand::SealNewBlock {
-						create_empty: true,  // 即使没有交易也创建空块
-						finalize: true,      // 立即最终化
-						parent_hash: None,   // 使用最长链的父哈希
-						sender: None,        // 不需要响应通道
-					})
-					.unwrap();
-				}
-			});
+			let commands_stream =
+				commands_stream.expect("command channel is created for manual seal; qed");
+			// 启动定时出块任务；未指定出块间隔时只通过 RPC 出块
+			if let Some(block_time) = block_time {
+				let mut sink =
+					command_sink.expect("command channel is created for manual seal; qed");
+				let metrics =
+					prometheus_registry.as_ref().map(ManualSealMetrics::register).transpose()?;
+				let pool = transaction_pool.clone();
+				// 包括通过 `engine_createBlock` RPC 出的块
+				let mut imports = client.import_notification_stream();
+				// 通过 `spawn_handle` 启动的任务会在任务管理器关闭时被取消
+				task_manager.spawn_handle().spawn("block_authoring", None, async move {
+					let mut last_seal = Instant::now();
+					loop {
+						// 等待指定的时间间隔
+						futures_timer::Delay::new(Duration::from_millis(block_time)).await;
+						// 等待期间导入过区块时，从本次 tick 开始重新计算空闲时间
+						while let Some(Some(_)) = imports.next().now_or_never() {
+							last_seal = Instant::now();
+						}
+						let SealDecision::Seal { create_empty } =
+							seal.should_seal(pool.status().ready, last_seal.elapsed())
+						else {
+							continue;
+						};
+						// 发送出块命令
+						let command = sc_consensus_manual_seal::EngineCommand::SealNewBlock {
+							create_empty,
+							// 未启用 GRANDPA 和延迟最终化时立即最终化
+							finalize: finality == Finality::None && finality_delay.is_none(),
+							parent_hash: None,   // 使用最长链的父哈希
+							sender: None,        // 不需要响应通道
+						};
+						match sink.try_send(command) {
+							Ok(()) => {},
+							// 出块工作线程处理过慢（例如正在导入一个很慢的区块），跳过本次出块，避免命令堆积
+							Err(err) if err.is_full() => {
+								log::warn!(
+									target: LOG_TARGET,
+									"Seal command queue is full, skipping this {block_time}ms tick"
+								);
+								if let Some(metrics) = &metrics {
+									metrics.skipped_seals.inc();
+								}
+							},
+							// 出块工作线程已经停止（例如节点正在关闭），定时器随之退出
+							Err(_) => {
+								log::debug!(
+									target: LOG_TARGET,
+									"Manual seal worker has stopped, shutting down the seal timer"
+								);
+								break;
+							},
+						}
+					}
+				});
+			}
 
 			let params = sc_consensus_manual_seal::ManualSealParams {
-				block_import: client.clone(),
+				block_import,
 				env: proposer,
-				client,
+				client: client.clone(),
 				pool: transaction_pool,
 				select_chain,
 				commands_stream: Box::pin(commands_stream),
 				consensus_data_provider: None,
-				create_inherent_data_providers: move |_, ()| async move {
-					Ok(sp_timestamp::InherentDataProvider::from_system_time())
-				},
+				create_inherent_data_providers: dev_inherent_data_providers(
+					client.clone(),
+					dev_clock.expect("dev clock is created for dev consensus modes; qed"),
+				),
 			};
 			let authorship_future = sc_consensus_manual_seal::run_manual_seal(params);
 
@@ -286,8 +737,157 @@ pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Ha
 				authorship_future,
 			);
 		},
-		/// 无共识：不启动出块机制
-		_ => {},
+		// Aura：密钥库中持有出块者密钥的验证人按 slot 轮流出块
+		Consensus::Aura => {
+			if is_authority {
+				let slot_duration = sc_consensus_aura::slot_duration(&*client)?;
+				let aura = sc_consensus_aura::start_aura::<AuraPair, _, _, _, _, _, _, _, _, _, _>(
+					sc_consensus_aura::StartAuraParams {
+						slot_duration,
+						client: client.clone(),
+						select_chain,
+						block_import,
+						proposer_factory: proposer,
+						create_inherent_data_providers: move |_, ()| async move {
+							let timestamp = sp_timestamp::InherentDataProvider::from_system_time();
+							let slot =
+								sp_consensus_aura::inherents::InherentDataProvider::from_timestamp_and_slot_duration(
+									*timestamp,
+									slot_duration,
+								);
+							Ok((slot, timestamp))
+						},
+						force_authoring,
+						backoff_authoring_blocks: Option::<()>::None,
+						keystore: keystore_container.keystore(),
+						sync_oracle: sync_service.clone(),
+						justification_sync_link: sync_service.clone(),
+						block_proposal_slot_portion: sc_consensus_aura::SlotProportion::new(2f32 / 3f32),
+						max_block_proposal_slot_portion: None,
+						telemetry: telemetry.as_ref().map(|x| x.handle()),
+						compatibility_mode: Default::default(),
+					},
+				)?;
+
+				task_manager
+					.spawn_essential_handle()
+					.spawn_blocking("aura", Some("block-authoring"), aura);
+			}
+		},
+		// BABE：出块者通过 VRF 竞争主 slot，没有出块者时由次级出块者补位
+		Consensus::Babe => {
+			let BabeParts { block_import, link, .. } =
+				babe.expect("`new_partial` creates BABE components for `--consensus babe`; qed");
+			if is_authority {
+				let slot_duration = link.config().slot_duration();
+				let babe = sc_consensus_babe::start_babe(sc_consensus_babe::BabeParams {
+					keystore: keystore_container.keystore(),
+					client,
+					select_chain,
+					env: proposer,
+					block_import,
+					sync_oracle: sync_service.clone(),
+					justification_sync_link: sync_service.clone(),
+					create_inherent_data_providers: move |_, ()| async move {
+						let timestamp = sp_timestamp::InherentDataProvider::from_system_time();
+						let slot =
+							sp_consensus_babe::inherents::InherentDataProvider::from_timestamp_and_slot_duration(
+								*timestamp,
+								slot_duration,
+							);
+						Ok((slot, timestamp))
+					},
+					force_authoring,
+					backoff_authoring_blocks: Option::<()>::None,
+					babe_link: link,
+					block_proposal_slot_portion: sc_consensus_babe::SlotProportion::new(0.5),
+					max_block_proposal_slot_portion: None,
+					telemetry: telemetry.as_ref().map(|x| x.handle()),
+				})?;
+
+				task_manager.spawn_essential_handle().spawn_blocking(
+					"babe-proposer",
+					Some("block-authoring"),
+					babe,
+				);
+			}
+		},
+		// 工作量证明：挖矿线程在任务管理器中运行，任何节点都可以挖矿，不需要出块者密钥
+		Consensus::Pow => {
+			if is_authority && pow_threads > 0 {
+				let algorithm = Blake2Algorithm::new(client.clone());
+				let timestamp_client = client.clone();
+				let (worker, worker_task) = sc_consensus_pow::start_mining_worker(
+					pow_block_import(client.clone(), block_import, select_chain.clone()),
+					client,
+					select_chain,
+					algorithm,
+					proposer,
+					sync_service.clone(),
+					sync_service.clone(),
+					None,
+					// 挖出父区块可能不到 `MinimumPeriod`，时间戳不能早于父区块加上该间隔
+					move |parent, ()| {
+						let client = timestamp_client.clone();
+						async move {
+							let parent_timestamp = dev_timestamp::parent_timestamp(&client, parent)?;
+							let now = sp_timestamp::Timestamp::current().as_millis();
+							let timestamp = dev_timestamp::clamp_to_minimum_period(parent_timestamp, now);
+							Ok(sp_timestamp::InherentDataProvider::new(timestamp.into()))
+						}
+					},
+					// 每 10 秒重新构建待挖区块，以便打包新到达的交易
+					Duration::from_secs(10),
+					Duration::from_secs(10),
+				);
+
+				task_manager.spawn_essential_handle().spawn_blocking(
+					"pow",
+					Some("block-authoring"),
+					worker_task,
+				);
+
+				// 各线程从不同的起点出发，以线程数为步长搜索 nonce，互不重复
+				let step = U256::from(pow_threads);
+				for index in 0..pow_threads {
+					let worker = worker.clone();
+					task_manager.spawn_handle().spawn_blocking(
+						"pow-miner",
+						Some("block-authoring"),
+						async move {
+							let mut current = None;
+							let mut nonce = U256::zero();
+							loop {
+								let Some(metadata) = worker.metadata() else {
+									// 待挖区块尚未构建好
+									futures_timer::Delay::new(Duration::from_millis(100)).await;
+									continue;
+								};
+								// 待挖区块或难度变化后，从本线程的起点重新搜索
+								let target = (metadata.pre_hash, metadata.difficulty);
+								if current != Some(target) {
+									current = Some(target);
+									nonce = U256::from(index);
+								}
+								if let Some(seal) = pow::mine(
+									metadata.pre_hash,
+									metadata.difficulty,
+									&mut nonce,
+									step,
+									POW_NONCES_PER_ROUND,
+								) {
+									worker.submit(seal.encode()).await;
+								}
+								// 让出线程，使节点关闭时可以停止挖矿任务
+								futures_timer::Delay::new(Duration::ZERO).await;
+							}
+						},
+					);
+				}
+			}
+		},
+		// 无共识：不启动出块机制
+		Consensus::None => {},
 	}
 
 	Ok(task_manager)
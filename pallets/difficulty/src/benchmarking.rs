@@ -0,0 +1,24 @@
+//! 难度 pallet 的基准测试
+
+use super::*;
+use frame::benchmarking::prelude::*;
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn set_difficulty() -> Result<(), BenchmarkError> {
+		let origin =
+			T::DifficultyOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let new = T::MinDifficulty::get().saturating_mul(U256::from(2));
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, new);
+
+		assert_eq!(Pallet::<T>::difficulty(), new);
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(1_000), crate::mock::Test);
+}
@@ -0,0 +1,133 @@
+//! 保存工作量证明难度的 pallet
+//!
+//! 节点在 `--consensus pow` 模式下通过 `sp_consensus_pow::DifficultyApi` 读取 [`Pallet::difficulty`]，
+//! 矿工需要找到一个哈希值乘以难度后不溢出 `U256` 的 nonce。难度由 [`Config::DifficultyOrigin`] 调整，
+//! 不会随出块时间自动变化，适合单机运行的教学链。
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame::prelude::*;
+use polkadot_sdk::{polkadot_sdk_frame as frame, sp_core::U256};
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+pub mod weights;
+pub use weights::*;
+
+// 重新导出所有 pallet 部分，这是将 pallet 正确导入运行时所需的
+pub use pallet::*;
+
+#[frame::pallet]
+pub mod pallet {
+	use super::*;
+
+	/// Pallet 配置 trait
+	#[pallet::config]
+	pub trait Config: polkadot_sdk::frame_system::Config<RuntimeEvent: From<Event<Self>>> {
+		/// 允许调用 [`Pallet::set_difficulty`] 的来源
+		/// 运行时中通常配置为 root
+		type DifficultyOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// 难度的下限，必须大于零
+		#[pallet::constant]
+		type MinDifficulty: Get<U256>;
+
+		/// 可调度函数的权重信息
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// 当前的工作量证明难度
+	/// 未设置或低于 [`Config::MinDifficulty`] 时，[`Pallet::difficulty`] 返回下限
+	#[pallet::storage]
+	pub type Difficulty<T: Config> = StorageValue<Value = U256, QueryKind = ValueQuery>;
+
+	/// 创世配置
+	#[pallet::genesis_config]
+	#[derive(DefaultNoBound)]
+	pub struct GenesisConfig<T: Config> {
+		/// 创世时的难度，为零时使用 [`Config::MinDifficulty`]
+		pub initial_difficulty: U256,
+		#[serde(skip)]
+		pub _marker: PhantomData<T>,
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+		fn build(&self) {
+			Difficulty::<T>::put(self.initial_difficulty.max(T::MinDifficulty::get()));
+		}
+	}
+
+	/// Pallet 事件
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// 难度已变更
+		DifficultySet {
+			/// 变更前的难度
+			old: U256,
+			/// 变更后的难度
+			new: U256,
+		},
+	}
+
+	/// Pallet 错误
+	#[pallet::error]
+	pub enum Error<T> {
+		/// 新的难度低于 [`Config::MinDifficulty`]
+		BelowMinimum,
+	}
+
+	/// 可调度函数（外部调用）
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// 将难度设置为 `new`，从下一个区块开始生效
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::set_difficulty())]
+		pub fn set_difficulty(origin: OriginFor<T>, new: U256) -> DispatchResult {
+			T::DifficultyOrigin::ensure_origin(origin)?;
+			ensure!(new >= T::MinDifficulty::get(), Error::<T>::BelowMinimum);
+
+			let old = Difficulty::<T>::get();
+			Difficulty::<T>::put(new);
+			Self::deposit_event(Event::DifficultySet { old, new });
+			Ok(())
+		}
+	}
+
+	/// 区块生命周期钩子
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// 检查配置常量是否合理
+		fn integrity_test() {
+			assert!(!T::MinDifficulty::get().is_zero(), "`MinDifficulty` must be greater than zero");
+		}
+
+		/// 检查存储的难度不低于下限
+		#[cfg(feature = "try-runtime")]
+		fn try_state(_n: BlockNumberFor<T>) -> Result<(), polkadot_sdk::sp_runtime::TryRuntimeError> {
+			ensure!(
+				Difficulty::<T>::get() >= T::MinDifficulty::get(),
+				"`Difficulty` is below `MinDifficulty`"
+			);
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// 返回当前生效的难度，不会低于 [`Config::MinDifficulty`]
+		pub fn difficulty() -> U256 {
+			Difficulty::<T>::get().max(T::MinDifficulty::get())
+		}
+	}
+}
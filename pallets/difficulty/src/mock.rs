@@ -0,0 +1,54 @@
+//! 难度 pallet 的测试运行时
+
+use crate as pallet_difficulty;
+use frame::testing_prelude::*;
+use polkadot_sdk::{polkadot_sdk_frame as frame, sp_core::U256};
+
+/// 测试账户
+pub const ALICE: u64 = 1;
+
+/// 难度的下限
+pub const MIN_DIFFICULTY: u64 = 100;
+
+construct_runtime!(
+	pub enum Test {
+		System: frame_system,
+		Difficulty: pallet_difficulty,
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+	type Block = MockBlock<Test>;
+}
+
+parameter_types! {
+	pub MinDifficulty: U256 = U256::from(MIN_DIFFICULTY);
+}
+
+impl pallet_difficulty::Config for Test {
+	type DifficultyOrigin = frame_system::EnsureRoot<u64>;
+	type MinDifficulty = MinDifficulty;
+	type WeightInfo = ();
+}
+
+/// 构建测试环境
+/// `initial_difficulty` 为创世时的难度
+pub fn new_test_ext(initial_difficulty: u64) -> TestState {
+	let storage = RuntimeGenesisConfig {
+		difficulty: pallet_difficulty::GenesisConfig {
+			initial_difficulty: initial_difficulty.into(),
+			..Default::default()
+		},
+		..Default::default()
+	}
+	.build_storage()
+	.unwrap();
+
+	let mut ext = TestState::new(storage);
+	ext.execute_with(|| {
+		// 创世区块中不会记录事件，因此从区块 1 开始
+		System::set_block_number(1);
+	});
+	ext
+}
@@ -0,0 +1,52 @@
+//! 难度 pallet 的单元测试
+
+use crate::{mock::*, Error, Event};
+use frame::testing_prelude::*;
+use polkadot_sdk::{polkadot_sdk_frame as frame, sp_core::U256};
+
+#[test]
+fn genesis_config_sets_initial_difficulty() {
+	new_test_ext(1_000).execute_with(|| {
+		assert_eq!(Difficulty::difficulty(), U256::from(1_000));
+	});
+}
+
+#[test]
+fn genesis_difficulty_is_clamped_to_minimum() {
+	new_test_ext(0).execute_with(|| {
+		assert_eq!(crate::Difficulty::<Test>::get(), U256::from(MIN_DIFFICULTY));
+		assert_eq!(Difficulty::difficulty(), U256::from(MIN_DIFFICULTY));
+	});
+}
+
+#[test]
+fn set_difficulty_works() {
+	new_test_ext(1_000).execute_with(|| {
+		assert_ok!(Difficulty::set_difficulty(RuntimeOrigin::root(), U256::from(2_000)));
+		assert_eq!(Difficulty::difficulty(), U256::from(2_000));
+		System::assert_last_event(
+			Event::DifficultySet { old: U256::from(1_000), new: U256::from(2_000) }.into(),
+		);
+	});
+}
+
+#[test]
+fn set_difficulty_requires_difficulty_origin() {
+	new_test_ext(1_000).execute_with(|| {
+		assert_noop!(
+			Difficulty::set_difficulty(RuntimeOrigin::signed(ALICE), U256::from(2_000)),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn set_difficulty_rejects_values_below_minimum() {
+	new_test_ext(1_000).execute_with(|| {
+		assert_noop!(
+			Difficulty::set_difficulty(RuntimeOrigin::root(), U256::from(MIN_DIFFICULTY - 1)),
+			Error::<Test>::BelowMinimum
+		);
+		assert_ok!(Difficulty::set_difficulty(RuntimeOrigin::root(), U256::from(MIN_DIFFICULTY)));
+	});
+}
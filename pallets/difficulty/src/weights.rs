@@ -0,0 +1,39 @@
+//! `pallet_difficulty` 的权重
+//!
+//! `set_difficulty` 只读写 `Difficulty` 一个存储项，目前的数值按这一次读写估计。
+//! 需要实际测量的权重时，执行 `scripts/benchmark-weights.sh pallet_difficulty` 重新生成此文件。
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame::{deps::frame_support::weights::constants::RocksDbWeight, prelude::*};
+use polkadot_sdk::polkadot_sdk_frame as frame;
+use core::marker::PhantomData;
+
+/// Weight functions needed for `pallet_difficulty`.
+pub trait WeightInfo {
+	fn set_difficulty() -> Weight;
+}
+
+/// `pallet_difficulty` 的估计权重，`proof_size` 按存储项的最大编码长度估计
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// 读取 `Difficulty::Difficulty` 1 次，写入 1 次
+	fn set_difficulty() -> Weight {
+		Weight::from_parts(7_000_000, 1517)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+	/// 读取 `Difficulty::Difficulty` 1 次，写入 1 次
+	fn set_difficulty() -> Weight {
+		Weight::from_parts(7_000_000, 1517)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+}
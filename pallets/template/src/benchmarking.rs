@@ -0,0 +1,180 @@
+//! 模板 pallet 的基准测试
+//!
+//! 用于测量每个可调度函数的执行成本，结果写入 `weights.rs`
+
+use super::*;
+use alloc::vec::Vec;
+use frame::benchmarking::prelude::*;
+use frame_system::RawOrigin;
+use polkadot_sdk::frame_support::traits::fungible::{Inspect, InspectHold, Mutate};
+
+/// 返回一个余额足以支付押金的账户
+fn funded_account<T: Config>(who: T::AccountId) -> T::AccountId {
+	let amount = T::ValueDeposit::get()
+		.saturating_mul(10u32.into())
+		.saturating_add(T::Currency::minimum_balance());
+	T::Currency::set_balance(&who, amount);
+	who
+}
+
+/// 写满 [`History`]，记录的区块号从 `first` 开始递增
+fn fill_history<T: Config>(first: u32) {
+	let entries = (0..T::MaxHistory::get())
+		.map(|i| (first.saturating_add(i).into(), MomentOf::<T>::default()))
+		.collect::<Vec<_>>();
+	History::<T>::put(BoundedVec::truncate_from(entries));
+}
+
+/// 为 `who` 认领一个值
+fn claimed_account<T: Config>(who: T::AccountId, value: u32) -> T::AccountId {
+	let who = funded_account::<T>(who);
+	Pallet::<T>::claim(RawOrigin::Signed(who.clone()).into(), value)
+		.expect("account is funded; qed");
+	who
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn set_value() {
+		let caller: T::AccountId = whitelisted_caller();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), 100);
+
+		assert_eq!(Pallet::<T>::get_value(), Some(100));
+	}
+
+	#[benchmark]
+	fn increment() {
+		Pallet::<T>::change_value(None, Some(0), None);
+		let caller: T::AccountId = whitelisted_caller();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), 1);
+
+		assert_eq!(Pallet::<T>::get_value(), Some(1));
+	}
+
+	#[benchmark]
+	fn decrement() {
+		Pallet::<T>::change_value(None, Some(1), None);
+		let caller: T::AccountId = whitelisted_caller();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), 1);
+
+		assert_eq!(Pallet::<T>::get_value(), Some(0));
+	}
+
+	#[benchmark]
+	fn reset() -> Result<(), BenchmarkError> {
+		Pallet::<T>::change_value(None, Some(1), None);
+		let origin =
+			T::ResetOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin);
+
+		assert_eq!(Pallet::<T>::get_value(), None);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn normalize_value() {
+		let max = T::MaxValue::get();
+		let current = max.saturating_add(1);
+		Pallet::<T>::change_value(None, Some(current), None);
+
+		#[block]
+		{
+			Pallet::<T>::normalize_value(current).expect("task can always be executed; qed");
+		}
+
+		assert_eq!(Pallet::<T>::get_value(), Some(max));
+	}
+
+	#[benchmark]
+	fn claim() {
+		let caller = funded_account::<T>(whitelisted_caller());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), 1);
+
+		assert_eq!(AccountValues::<T>::get(&caller), Some(1));
+		assert_eq!(
+			T::Currency::balance_on_hold(&HoldReason::ValueDeposit.into(), &caller),
+			T::ValueDeposit::get()
+		);
+	}
+
+	#[benchmark]
+	fn update() {
+		let caller = claimed_account::<T>(whitelisted_caller(), 1);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), 2);
+
+		assert_eq!(AccountValues::<T>::get(&caller), Some(2));
+	}
+
+	#[benchmark]
+	fn transfer_ownership() {
+		let caller = claimed_account::<T>(whitelisted_caller(), 1);
+		let new_owner = funded_account::<T>(account("new_owner", 0, 0));
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), new_owner.clone());
+
+		assert_eq!(AccountValues::<T>::get(&caller), None);
+		assert_eq!(AccountValues::<T>::get(&new_owner), Some(1));
+	}
+
+	#[benchmark]
+	fn release() {
+		let caller = claimed_account::<T>(whitelisted_caller(), 1);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()));
+
+		assert_eq!(AccountValues::<T>::get(&caller), None);
+		assert_eq!(
+			T::Currency::balance_on_hold(&HoldReason::ValueDeposit.into(), &caller),
+			0u32.into()
+		);
+	}
+
+	#[benchmark]
+	fn record_timestamp() {
+		// 写满历史记录，以覆盖丢弃最旧记录的路径
+		fill_history::<T>(0);
+		let n = frame_system::Pallet::<T>::block_number();
+
+		#[block]
+		{
+			Pallet::<T>::on_finalize(n);
+		}
+
+		assert_eq!(History::<T>::get().len() as u32, T::MaxHistory::get());
+	}
+
+	#[benchmark]
+	fn prune_history() {
+		// 所有记录都已过期，以覆盖清理最多记录的路径
+		fill_history::<T>(0);
+		let n = T::HistoryRetention::get()
+			.saturating_add(T::MaxHistory::get().into())
+			.saturating_add(1u32.into());
+
+		#[block]
+		{
+			Pallet::<T>::on_idle(n, Weight::MAX);
+		}
+
+		assert!(History::<T>::get().is_empty());
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(None), crate::mock::Test);
+}
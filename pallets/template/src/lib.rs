@@ -1,14 +1,58 @@
 //! 使用 [`frame`] 构建的模板 pallet
 //!
 //! 这是一个最小化的 pallet 模板，作为创建自定义 pallet 的起点
-//! 
+//!
 //! 要开始使用此 pallet，请尝试实现以下指南：
 //! <https://paritytech.github.io/polkadot-sdk/master/polkadot_sdk_docs/guides/your_first_pallet/index.html>
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+
 use frame::prelude::*;
-use polkadot_sdk::polkadot_sdk_frame as frame;
+use frame_system::offchain::{CreateBare, SubmitTransaction};
+use polkadot_sdk::{
+	frame_support::traits::{
+		fungible::{self, InspectHold, MutateHold},
+		tokens::{Fortitude, Precision, Restriction},
+		Time,
+	},
+	polkadot_sdk_frame as frame,
+	sp_runtime::{
+		offchain::storage::{StorageRetrievalError, StorageValueRef},
+		traits::Zero,
+	},
+};
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+pub mod migrations;
+pub mod weights;
+pub use weights::*;
+
+/// 日志目标
+const LOG_TARGET: &str = "runtime::template";
+
+/// 链下本地存储中记录最近一次提交的 `normalize_value` 任务的键
+const NORMALIZE_SUBMITTED_KEY: &[u8] = b"template::normalize_value";
+
+/// 提交 `normalize_value` 任务后等待的区块数，超过后 `Value` 仍未恢复时才重新提交
+const NORMALIZE_RESUBMIT_BLOCKS: u32 = 5;
+
+/// [`Config::Currency`] 的余额类型
+pub type BalanceOf<T> = <<T as Config>::Currency as fungible::Inspect<
+	<T as polkadot_sdk::frame_system::Config>::AccountId,
+>>::Balance;
+
+/// [`Config::Time`] 的时间戳类型
+pub type MomentOf<T> = <<T as Config>::Time as Time>::Moment;
 
 // 重新导出所有 pallet 部分，这是将 pallet 正确导入运行时所需的
 pub use pallet::*;
@@ -20,15 +64,501 @@ pub mod pallet {
 	/// Pallet 配置 trait
 	/// 定义此 pallet 所需的配置类型
 	#[pallet::config]
-	pub trait Config: polkadot_sdk::frame_system::Config {}
+	pub trait Config:
+		polkadot_sdk::frame_system::Config<RuntimeEvent: From<Event<Self>>>
+		+ CreateBare<frame_system::Call<Self>>
+	{
+		/// 运行时任务类型
+		/// 用于将本 pallet 的 [`Task`] 转换为 `frame_system::Call::do_task` 可以执行的任务
+		type RuntimeTask: polkadot_sdk::frame_support::traits::Task
+			+ IsType<<Self as polkadot_sdk::frame_system::Config>::RuntimeTask>
+			+ From<Task<Self>>;
+
+		/// 允许调用 [`Pallet::reset`] 的来源
+		/// 运行时中通常配置为 root
+		type ResetOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// `Value` 的上限
+		/// 超出上限的值会被 [`Pallet::normalize_value`] 任务恢复为上限
+		#[pallet::constant]
+		type MaxValue: Get<u32>;
+
+		/// 运行时的保留原因类型，必须能够由本 pallet 的 [`HoldReason`] 转换而来
+		type RuntimeHoldReason: From<HoldReason>;
+
+		/// 用于保留账户值押金的货币
+		type Currency: fungible::Mutate<Self::AccountId>
+			+ fungible::MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>;
+
+		/// 认领账户值时需要保留的押金
+		#[pallet::constant]
+		type ValueDeposit: Get<BalanceOf<Self>>;
+
+		/// 提供当前区块时间戳的时间源，运行时中通常为 `pallet_timestamp`
+		type Time: Time;
+
+		/// [`History`] 中最多保存的记录数，写满后会覆盖最旧的记录
+		#[pallet::constant]
+		type MaxHistory: Get<u32>;
+
+		/// [`History`] 中记录的保留区块数，更早的记录会在 `on_idle` 中被清理
+		/// 必须小于 [`Config::MaxHistory`]，否则记录在过期之前就已被覆盖，`on_idle` 无事可做
+		#[pallet::constant]
+		type HistoryRetention: Get<BlockNumberFor<Self>>;
+
+		/// 可调度函数的权重信息
+		type WeightInfo: WeightInfo;
+	}
+
+	/// 当前的存储版本
+	/// 修改存储布局时需要递增此版本，并在 [`crate::migrations`] 中添加相应的迁移
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
 
 	/// Pallet 结构体
 	/// 这是 pallet 的主要结构，使用 `()` 表示不需要存储任何数据
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
+	/// `Value` 中保存的内容
+	#[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+	pub struct ValueInfo<AccountId, BlockNumber> {
+		/// 当前的值
+		pub value: u32,
+		/// 最后一次设置该值的账户，由特权来源、任务、创世配置或迁移写入时为 `None`
+		pub setter: Option<AccountId>,
+		/// 最后一次写入该值的区块号
+		pub updated_at: BlockNumber,
+	}
+
+	/// 运行时中使用的 [`ValueInfo`] 类型
+	pub type ValueInfoOf<T> =
+		ValueInfo<<T as polkadot_sdk::frame_system::Config>::AccountId, BlockNumberFor<T>>;
+
 	/// 存储值
-	/// 存储一个 u32 类型的值
+	/// 存储一个 u32 类型的值以及最后一次修改它的账户和区块号
+	#[pallet::storage]
+	pub type Value<T: Config> = StorageValue<Value = ValueInfoOf<T>>;
+
+	/// 每个账户拥有的值
+	/// 账户通过 [`Pallet::claim`] 认领一个值，并为此保留 [`Config::ValueDeposit`] 押金
 	#[pallet::storage]
-	pub type Value<T> = StorageValue<Value = u32>;
+	pub type AccountValues<T: Config> =
+		StorageMap<Hasher = Blake2_128Concat, Key = T::AccountId, Value = u32>;
+
+	/// 最近区块的时间戳历史
+	/// 这是一个环形缓冲区：每个区块在 `on_finalize` 中追加一条记录，写满后丢弃最旧的记录
+	#[pallet::storage]
+	pub type History<T: Config> = StorageValue<
+		Value = BoundedVec<(BlockNumberFor<T>, MomentOf<T>), T::MaxHistory>,
+		QueryKind = ValueQuery,
+	>;
+
+	/// 本 pallet 保留资金的原因
+	#[pallet::composite_enum]
+	pub enum HoldReason {
+		/// 认领账户值时保留的押金
+		ValueDeposit,
+	}
+
+	/// 创世配置
+	#[pallet::genesis_config]
+	#[derive(DefaultNoBound)]
+	pub struct GenesisConfig<T: Config> {
+		/// 创世时写入 `Value` 的初始值，为 `None` 时保持未设置
+		pub initial_value: Option<u32>,
+		#[serde(skip)]
+		pub _marker: PhantomData<T>,
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+		fn build(&self) {
+			if let Some(value) = self.initial_value {
+				Value::<T>::put(ValueInfo {
+					value,
+					setter: None,
+					updated_at: frame_system::Pallet::<T>::block_number(),
+				});
+			}
+		}
+	}
+
+	/// Pallet 事件
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// `Value` 已变更
+		ValueChanged {
+			/// 变更前的值，未设置时为 `None`
+			old: Option<u32>,
+			/// 变更后的值，被清除时为 `None`
+			new: Option<u32>,
+			/// 发起变更的账户，由特权来源（如 root）发起时为 `None`
+			who: Option<T::AccountId>,
+		},
+		/// 账户认领了一个值并保留了押金
+		ValueClaimed {
+			/// 认领者
+			who: T::AccountId,
+			/// 认领时的值
+			value: u32,
+			/// 保留的押金
+			deposit: BalanceOf<T>,
+		},
+		/// 账户值已更新
+		AccountValueUpdated {
+			/// 值的拥有者
+			who: T::AccountId,
+			/// 更新前的值
+			old: u32,
+			/// 更新后的值
+			new: u32,
+		},
+		/// 账户值的所有权连同押金一起转移给了新的拥有者
+		OwnershipTransferred {
+			/// 原拥有者
+			from: T::AccountId,
+			/// 新拥有者
+			to: T::AccountId,
+			/// 转移的值
+			value: u32,
+		},
+		/// 账户值已释放，押金已退还
+		ValueReleased {
+			/// 原拥有者
+			who: T::AccountId,
+			/// 释放时的值
+			value: u32,
+			/// 退还的押金
+			deposit: BalanceOf<T>,
+		},
+	}
+
+	/// Pallet 错误
+	#[pallet::error]
+	pub enum Error<T> {
+		/// 增加后的值超出 `u32` 的范围
+		Overflow,
+		/// 减少后的值小于零
+		Underflow,
+		/// `Value` 尚未设置
+		NoneValue,
+		/// 账户已经拥有一个值
+		AlreadyClaimed,
+		/// 账户没有认领任何值
+		NotClaimed,
+		/// 不能将所有权转移给自己
+		TransferToSelf,
+	}
+
+	/// 可调度函数（外部调用）
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// 将 `Value` 设置为 `new`，覆盖原有的值
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::set_value())]
+		pub fn set_value(origin: OriginFor<T>, new: u32) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let old = Self::get_value();
+			Self::change_value(old, Some(new), Some(who));
+			Ok(())
+		}
+
+		/// 将 `Value` 增加 `by`，溢出时返回 [`Error::Overflow`]
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::increment())]
+		pub fn increment(origin: OriginFor<T>, by: u32) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let old = Self::get_value().ok_or(Error::<T>::NoneValue)?;
+			let new = old.checked_add(by).ok_or(Error::<T>::Overflow)?;
+			Self::change_value(Some(old), Some(new), Some(who));
+			Ok(())
+		}
+
+		/// 将 `Value` 减少 `by`，下溢时返回 [`Error::Underflow`]
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::decrement())]
+		pub fn decrement(origin: OriginFor<T>, by: u32) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let old = Self::get_value().ok_or(Error::<T>::NoneValue)?;
+			let new = old.checked_sub(by).ok_or(Error::<T>::Underflow)?;
+			Self::change_value(Some(old), Some(new), Some(who));
+			Ok(())
+		}
+
+		/// 清除 `Value`，只能由 [`Config::ResetOrigin`] 调用
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::reset())]
+		pub fn reset(origin: OriginFor<T>) -> DispatchResult {
+			T::ResetOrigin::ensure_origin(origin)?;
+			let old = Self::get_value().ok_or(Error::<T>::NoneValue)?;
+			Self::change_value(Some(old), None, None);
+			Ok(())
+		}
+
+		/// 为调用者认领一个值，并保留 [`Config::ValueDeposit`] 押金
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::claim())]
+		pub fn claim(origin: OriginFor<T>, value: u32) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(!AccountValues::<T>::contains_key(&who), Error::<T>::AlreadyClaimed);
+
+			let deposit = T::ValueDeposit::get();
+			T::Currency::hold(&HoldReason::ValueDeposit.into(), &who, deposit)?;
+			AccountValues::<T>::insert(&who, value);
+
+			Self::deposit_event(Event::ValueClaimed { who, value, deposit });
+			Ok(())
+		}
+
+		/// 更新调用者拥有的值
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::update())]
+		pub fn update(origin: OriginFor<T>, new: u32) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let old = AccountValues::<T>::get(&who).ok_or(Error::<T>::NotClaimed)?;
+			AccountValues::<T>::insert(&who, new);
+
+			Self::deposit_event(Event::AccountValueUpdated { who, old, new });
+			Ok(())
+		}
+
+		/// 将调用者拥有的值转移给 `new_owner`
+		///
+		/// 押金仍然处于保留状态，并随值一起转移给新的拥有者，由其在释放时取回。
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::transfer_ownership())]
+		pub fn transfer_ownership(origin: OriginFor<T>, new_owner: T::AccountId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(who != new_owner, Error::<T>::TransferToSelf);
+			ensure!(!AccountValues::<T>::contains_key(&new_owner), Error::<T>::AlreadyClaimed);
+			let value = AccountValues::<T>::take(&who).ok_or(Error::<T>::NotClaimed)?;
+
+			let reason = HoldReason::ValueDeposit.into();
+			let deposit = T::Currency::balance_on_hold(&reason, &who);
+			T::Currency::transfer_on_hold(
+				&reason,
+				&who,
+				&new_owner,
+				deposit,
+				Precision::Exact,
+				Restriction::OnHold,
+				Fortitude::Polite,
+			)?;
+			AccountValues::<T>::insert(&new_owner, value);
+
+			Self::deposit_event(Event::OwnershipTransferred { from: who, to: new_owner, value });
+			Ok(())
+		}
+
+		/// 释放调用者拥有的值，并退还押金
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::release())]
+		pub fn release(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let value = AccountValues::<T>::take(&who).ok_or(Error::<T>::NotClaimed)?;
+			let deposit = T::Currency::release_all(
+				&HoldReason::ValueDeposit.into(),
+				&who,
+				Precision::BestEffort,
+			)?;
+
+			Self::deposit_event(Event::ValueReleased { who, value, deposit });
+			Ok(())
+		}
+	}
+
+	/// 区块生命周期钩子
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// 预留 `on_finalize` 记录时间戳所需的权重
+		fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
+			T::WeightInfo::record_timestamp()
+		}
+
+		/// 将本区块的时间戳写入 [`History`]
+		/// 时间戳由区块中的内在交易设置，因此只能在 `on_finalize` 中读取
+		fn on_finalize(n: BlockNumberFor<T>) {
+			let now = T::Time::now();
+			History::<T>::mutate(|history| {
+				if history.is_full() {
+					history.remove(0);
+				}
+				// 上面已经腾出了空间，除非 `MaxHistory` 为零（由 `integrity_test` 排除）
+				let _ = history.try_push((n, now));
+			});
+		}
+
+		/// 在区块剩余的权重足够时，清理超出 [`Config::HistoryRetention`] 的记录
+		fn on_idle(n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			let weight = T::WeightInfo::prune_history();
+			if remaining_weight.any_lt(weight) {
+				return Weight::zero();
+			}
+
+			let cutoff = n.saturating_sub(T::HistoryRetention::get());
+			let mut history = History::<T>::get();
+			let stale = history.iter().take_while(|(block, _)| *block < cutoff).count();
+			if stale > 0 {
+				history.drain(..stale);
+				History::<T>::put(history);
+			}
+			weight
+		}
+
+		/// 检查配置常量是否合理
+		fn integrity_test() {
+			assert!(T::MaxHistory::get() > 0, "`MaxHistory` must be greater than zero");
+			assert!(
+				!T::HistoryRetention::get().is_zero(),
+				"`HistoryRetention` must be greater than zero"
+			);
+			assert!(
+				T::HistoryRetention::get() < T::MaxHistory::get().into(),
+				"`HistoryRetention` must be less than `MaxHistory`"
+			);
+			assert!(T::MaxValue::get() > 0, "`MaxValue` must be greater than zero");
+		}
+
+		/// 检查 pallet 的存储不变量，详见 [`Pallet::do_try_state`]
+		#[cfg(feature = "try-runtime")]
+		fn try_state(_n: BlockNumberFor<T>) -> Result<(), polkadot_sdk::sp_runtime::TryRuntimeError> {
+			Self::do_try_state()
+		}
+
+		/// 当 `Value` 超出上限时，以无签名交易的形式提交 [`Pallet::normalize_value`] 任务
+		/// 同一个任务在 `NORMALIZE_RESUBMIT_BLOCKS` 个区块内只提交一次
+		fn offchain_worker(block_number: BlockNumberFor<T>) {
+			let Some(current) = Self::get_value() else { return };
+			if !Self::exceeds_max(current) {
+				return;
+			}
+
+			// 任务仍在交易池中时，重复提交只会被交易池拒绝
+			// 记录提交时的值和区块号，等待若干区块后再重试
+			let submitted = StorageValueRef::persistent(NORMALIZE_SUBMITTED_KEY);
+			let claimed = submitted.mutate(
+				|last: Result<Option<(u32, BlockNumberFor<T>)>, StorageRetrievalError>| match last {
+					Ok(Some((value, at)))
+						if value == current &&
+							block_number < at.saturating_add(NORMALIZE_RESUBMIT_BLOCKS.into()) =>
+						Err(()),
+					_ => Ok((current, block_number)),
+				},
+			);
+			if claimed.is_err() {
+				log::debug!(target: LOG_TARGET, "normalize_value task is already pending");
+				return;
+			}
+
+			let task = <T as Config>::RuntimeTask::from(Task::<T>::NormalizeValue { current });
+			let call = frame_system::Call::<T>::do_task { task: task.into() };
+			let xt = <T as CreateBare<frame_system::Call<T>>>::create_bare(call.into());
+			match SubmitTransaction::<T, frame_system::Call<T>>::submit_transaction(xt) {
+				Ok(()) => log::debug!(target: LOG_TARGET, "Submitted normalize_value task"),
+				Err(e) => log::error!(target: LOG_TARGET, "Failed to submit task: {:?}", e),
+			}
+		}
+	}
+
+	/// 运行时任务
+	/// 任何人都可以通过 `frame_system::Call::do_task` 执行满足条件的任务
+	#[pallet::tasks_experimental]
+	impl<T: Config> Pallet<T> {
+		/// 将超出 [`Config::MaxValue`] 的 `Value` 恢复为上限
+		#[pallet::task_list(Self::get_value().into_iter())]
+		#[pallet::task_condition(|current| Self::get_value() == Some(*current) && Self::exceeds_max(*current))]
+		#[pallet::task_weight(T::WeightInfo::normalize_value())]
+		#[pallet::task_index(0)]
+		pub fn normalize_value(current: u32) -> DispatchResult {
+			Self::change_value(Some(current), Some(T::MaxValue::get()), None);
+			Ok(())
+		}
+	}
+
+	/// 视图函数
+	/// 前端可以通过 `RuntimeViewFunction` 运行时 API 直接查询类型化的结果，无需自行计算存储键
+	#[pallet::view_functions]
+	impl<T: Config> Pallet<T> {
+		/// 返回当前的 `Value`，未设置时为 `None`
+		pub fn get_value() -> Option<u32> {
+			Value::<T>::get().map(|info| info.value)
+		}
+
+		/// 返回当前的 `Value` 以及最后一次修改它的账户和区块号
+		pub fn get_value_info() -> Option<ValueInfoOf<T>> {
+			Value::<T>::get()
+		}
+
+		/// `Value` 是否已设置
+		pub fn is_set() -> bool {
+			Value::<T>::exists()
+		}
+
+		/// 返回 `who` 拥有的值，未认领时为 `None`
+		pub fn get_account_value(who: T::AccountId) -> Option<u32> {
+			AccountValues::<T>::get(who)
+		}
+	}
+
+	#[cfg(any(feature = "try-runtime", test))]
+	impl<T: Config> Pallet<T> {
+		/// 检查 pallet 的存储不变量：
+		///
+		/// - `Value` 的 `updated_at` 不晚于当前区块；
+		/// - [`History`] 中的记录按区块号严格递增，且不晚于当前区块；
+		/// - [`AccountValues`] 中的每个拥有者都保留了押金。
+		pub fn do_try_state() -> Result<(), polkadot_sdk::sp_runtime::TryRuntimeError> {
+			let now = frame_system::Pallet::<T>::block_number();
+
+			if let Some(info) = Value::<T>::get() {
+				ensure!(info.updated_at <= now, "`Value` was updated in a future block");
+			}
+
+			let history = History::<T>::get();
+			ensure!(
+				history.windows(2).all(|pair| pair[0].0 < pair[1].0),
+				"`History` must be sorted by block number without duplicates"
+			);
+			ensure!(
+				history.last().map_or(true, |(block, _)| *block <= now),
+				"`History` contains an entry from a future block"
+			);
+
+			if !T::ValueDeposit::get().is_zero() {
+				let reason = HoldReason::ValueDeposit.into();
+				for who in AccountValues::<T>::iter_keys() {
+					ensure!(
+						!T::Currency::balance_on_hold(&reason, &who).is_zero(),
+						"Every account value must be backed by a deposit"
+					);
+				}
+			}
+
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// `value` 是否超出了 [`Config::MaxValue`]
+		fn exceeds_max(value: u32) -> bool {
+			value > T::MaxValue::get()
+		}
+
+		/// 写入新值并发出 [`Event::ValueChanged`] 事件
+		pub(crate) fn change_value(
+			old: Option<u32>,
+			new: Option<u32>,
+			who: Option<T::AccountId>,
+		) {
+			let info = new.map(|value| ValueInfo {
+				value,
+				setter: who.clone(),
+				updated_at: frame_system::Pallet::<T>::block_number(),
+			});
+			Value::<T>::set(info);
+			Self::deposit_event(Event::ValueChanged { old, new, who });
+		}
+	}
 }
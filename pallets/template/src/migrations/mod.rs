@@ -0,0 +1,5 @@
+//! 模板 pallet 的存储迁移
+//!
+//! 每个模块负责将存储从上一个版本迁移到对应版本
+
+pub mod v1;
@@ -0,0 +1,68 @@
+//! 将 `Value` 从单独的 `u32` 迁移为 [`ValueInfo`]
+
+use crate::{Config, Pallet, Value, ValueInfo};
+use frame::prelude::*;
+use polkadot_sdk::{
+	frame_support::{migrations::VersionedMigration, traits::UncheckedOnRuntimeUpgrade},
+	polkadot_sdk_frame as frame,
+};
+
+#[cfg(feature = "try-runtime")]
+use alloc::vec::Vec;
+
+/// 迁移前（存储版本 0）的存储格式
+pub mod v0 {
+	use super::*;
+
+	/// 版本 0 中的 `Value`，只保存一个 `u32`
+	#[polkadot_sdk::frame_support::storage_alias]
+	pub type Value<T: Config> = StorageValue<Pallet<T>, u32>;
+}
+
+/// 不检查存储版本的 v0 -> v1 迁移
+///
+/// 旧值会保留，`setter` 设置为 `None`，`updated_at` 设置为执行迁移的区块号。
+/// 请使用带有版本检查的 [`MigrateV0ToV1`]。
+pub struct UncheckedMigrateToV1<T>(PhantomData<T>);
+
+impl<T: Config> UncheckedOnRuntimeUpgrade for UncheckedMigrateToV1<T> {
+	fn on_runtime_upgrade() -> Weight {
+		match v0::Value::<T>::take() {
+			Some(value) => {
+				Value::<T>::put(ValueInfo {
+					value,
+					setter: None,
+					updated_at: frame_system::Pallet::<T>::block_number(),
+				});
+				// 删除旧值和写入新值各算一次写入
+				T::DbWeight::get().reads_writes(1, 2)
+			},
+			None => T::DbWeight::get().reads(1),
+		}
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, polkadot_sdk::sp_runtime::TryRuntimeError> {
+		Ok(v0::Value::<T>::get().encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), polkadot_sdk::sp_runtime::TryRuntimeError> {
+		let old = Option::<u32>::decode(&mut &state[..])
+			.map_err(|_| "Failed to decode the pre-upgrade value")?;
+		let new = Value::<T>::get().map(|info| info.value);
+		ensure!(old == new, "Value must be preserved by the migration");
+		Ok(())
+	}
+}
+
+/// v0 -> v1 迁移
+///
+/// 仅在链上存储版本为 0 时执行 [`UncheckedMigrateToV1`]，完成后将存储版本设置为 1。
+pub type MigrateV0ToV1<T> = VersionedMigration<
+	0,
+	1,
+	UncheckedMigrateToV1<T>,
+	Pallet<T>,
+	<T as polkadot_sdk::frame_system::Config>::DbWeight,
+>;
@@ -0,0 +1,126 @@
+//! 模板 pallet 的测试运行时
+//!
+//! 包含 `frame_system`、`pallet_balances`、`pallet_timestamp` 以及模板 pallet 本身
+
+use crate as pallet_minimal_template;
+use frame::testing_prelude::*;
+use polkadot_sdk::{
+	pallet_balances, pallet_timestamp, polkadot_sdk_frame as frame,
+	sp_core::offchain::{
+		testing::{TestOffchainExt, TestTransactionPoolExt},
+		OffchainDbExt, OffchainWorkerExt, TransactionPoolExt,
+	},
+};
+
+/// 测试账户
+pub const ALICE: u64 = 1;
+pub const BOB: u64 = 2;
+pub const CHARLIE: u64 = 3;
+
+/// `Value` 的上限
+pub const MAX_VALUE: u32 = 1_000;
+
+/// 时间戳历史的最大长度
+pub const MAX_HISTORY: u32 = 3;
+
+/// 时间戳历史保留的区块数
+pub const HISTORY_RETENTION: u64 = 2;
+
+/// 认领账户值时保留的押金
+pub const VALUE_DEPOSIT: u64 = 100;
+
+/// 测试账户的初始余额
+pub const INITIAL_BALANCE: u64 = 1_000;
+
+construct_runtime!(
+	pub enum Test {
+		System: frame_system,
+		Balances: pallet_balances,
+		Timestamp: pallet_timestamp,
+		Template: pallet_minimal_template,
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+	type Block = MockBlock<Test>;
+	type AccountData = pallet_balances::AccountData<u64>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+	type AccountStore = System;
+}
+
+#[derive_impl(pallet_timestamp::config_preludes::TestDefaultConfig)]
+impl pallet_timestamp::Config for Test {}
+
+impl<LocalCall> frame_system::offchain::CreateTransactionBase<LocalCall> for Test
+where
+	RuntimeCall: From<LocalCall>,
+{
+	type RuntimeCall = RuntimeCall;
+	type Extrinsic = MockUncheckedExtrinsic<Test>;
+}
+
+impl<LocalCall> frame_system::offchain::CreateBare<LocalCall> for Test
+where
+	RuntimeCall: From<LocalCall>,
+{
+	fn create_bare(call: Self::RuntimeCall) -> Self::Extrinsic {
+		MockUncheckedExtrinsic::<Test>::new_bare(call)
+	}
+}
+
+impl pallet_minimal_template::Config for Test {
+	type RuntimeTask = RuntimeTask;
+	type ResetOrigin = frame_system::EnsureRoot<u64>;
+	type MaxValue = ConstU32<MAX_VALUE>;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type Currency = Balances;
+	type ValueDeposit = ConstU64<VALUE_DEPOSIT>;
+	type Time = Timestamp;
+	type MaxHistory = ConstU32<MAX_HISTORY>;
+	type HistoryRetention = ConstU64<HISTORY_RETENTION>;
+	type WeightInfo = ();
+}
+
+/// 构建测试环境
+/// `initial_value` 为模板 pallet 中 `Value` 的初始值
+pub fn new_test_ext(initial_value: Option<u32>) -> TestState {
+	let storage = RuntimeGenesisConfig {
+		balances: pallet_balances::GenesisConfig {
+			balances: vec![
+				(ALICE, INITIAL_BALANCE),
+				(BOB, INITIAL_BALANCE),
+				(CHARLIE, INITIAL_BALANCE),
+			],
+			..Default::default()
+		},
+		template: pallet_minimal_template::GenesisConfig { initial_value, ..Default::default() },
+		..Default::default()
+	}
+	.build_storage()
+	.unwrap();
+
+	let mut ext = TestState::new(storage);
+	ext.execute_with(|| {
+		// 创世区块中不会记录事件，因此从区块 1 开始
+		System::set_block_number(1);
+	});
+	ext
+}
+
+/// 构建可以运行链下工作机的测试环境，注册了本地存储和交易池扩展
+/// 返回的闭包给出已提交到交易池的交易
+pub fn new_offchain_test_ext(
+	initial_value: Option<u32>,
+) -> (TestState, impl Fn() -> Vec<Vec<u8>>) {
+	let mut ext = new_test_ext(initial_value);
+	let (offchain, _) = TestOffchainExt::new();
+	let (pool, pool_state) = TestTransactionPoolExt::new();
+	ext.register_extension(OffchainDbExt::new(offchain.clone()));
+	ext.register_extension(OffchainWorkerExt::new(offchain));
+	ext.register_extension(TransactionPoolExt::new(pool));
+	(ext, move || pool_state.read().transactions.clone())
+}
@@ -0,0 +1,554 @@
+//! 模板 pallet 的单元测试
+
+use crate::{
+	migrations, mock::*, AccountValues, Error, Event, History, HoldReason, Task, Value, ValueInfo,
+};
+use frame::testing_prelude::*;
+use polkadot_sdk::{
+	frame_support::traits::{fungible::InspectHold, Task as _},
+	polkadot_sdk_frame as frame,
+};
+
+#[test]
+fn genesis_config_sets_initial_value() {
+	new_test_ext(Some(42)).execute_with(|| {
+		assert_eq!(Template::get_value(), Some(42));
+	});
+	new_test_ext(None).execute_with(|| {
+		assert_eq!(Template::get_value(), None);
+	});
+}
+
+#[test]
+fn set_value_works() {
+	new_test_ext(None).execute_with(|| {
+		System::set_block_number(3);
+		assert_ok!(Template::set_value(RuntimeOrigin::signed(ALICE), 42));
+		assert_eq!(
+			Value::<Test>::get(),
+			Some(ValueInfo { value: 42, setter: Some(ALICE), updated_at: 3 })
+		);
+		System::assert_last_event(
+			Event::ValueChanged { old: None, new: Some(42), who: Some(ALICE) }.into(),
+		);
+	});
+}
+
+#[test]
+fn set_value_overwrites_existing_value() {
+	new_test_ext(Some(7)).execute_with(|| {
+		assert_ok!(Template::set_value(RuntimeOrigin::signed(BOB), 8));
+		assert_eq!(Template::get_value(), Some(8));
+		System::assert_last_event(
+			Event::ValueChanged { old: Some(7), new: Some(8), who: Some(BOB) }.into(),
+		);
+	});
+}
+
+#[test]
+fn set_value_requires_signed_origin() {
+	new_test_ext(None).execute_with(|| {
+		assert_noop!(Template::set_value(RuntimeOrigin::root(), 42), DispatchError::BadOrigin);
+		assert_noop!(Template::set_value(RuntimeOrigin::none(), 42), DispatchError::BadOrigin);
+	});
+}
+
+#[test]
+fn increment_works() {
+	new_test_ext(Some(10)).execute_with(|| {
+		assert_ok!(Template::increment(RuntimeOrigin::signed(ALICE), 5));
+		assert_eq!(Template::get_value(), Some(15));
+		System::assert_last_event(
+			Event::ValueChanged { old: Some(10), new: Some(15), who: Some(ALICE) }.into(),
+		);
+	});
+}
+
+#[test]
+fn increment_fails_without_value() {
+	new_test_ext(None).execute_with(|| {
+		assert_noop!(
+			Template::increment(RuntimeOrigin::signed(ALICE), 1),
+			Error::<Test>::NoneValue
+		);
+	});
+}
+
+#[test]
+fn increment_fails_on_overflow() {
+	new_test_ext(Some(u32::MAX - 1)).execute_with(|| {
+		assert_noop!(
+			Template::increment(RuntimeOrigin::signed(ALICE), 2),
+			Error::<Test>::Overflow
+		);
+		assert_ok!(Template::increment(RuntimeOrigin::signed(ALICE), 1));
+		assert_eq!(Template::get_value(), Some(u32::MAX));
+	});
+}
+
+#[test]
+fn increment_requires_signed_origin() {
+	new_test_ext(Some(1)).execute_with(|| {
+		assert_noop!(Template::increment(RuntimeOrigin::root(), 1), DispatchError::BadOrigin);
+	});
+}
+
+#[test]
+fn decrement_works() {
+	new_test_ext(Some(10)).execute_with(|| {
+		assert_ok!(Template::decrement(RuntimeOrigin::signed(BOB), 4));
+		assert_eq!(Template::get_value(), Some(6));
+		System::assert_last_event(
+			Event::ValueChanged { old: Some(10), new: Some(6), who: Some(BOB) }.into(),
+		);
+	});
+}
+
+#[test]
+fn decrement_fails_without_value() {
+	new_test_ext(None).execute_with(|| {
+		assert_noop!(
+			Template::decrement(RuntimeOrigin::signed(ALICE), 1),
+			Error::<Test>::NoneValue
+		);
+	});
+}
+
+#[test]
+fn decrement_fails_on_underflow() {
+	new_test_ext(Some(1)).execute_with(|| {
+		assert_noop!(
+			Template::decrement(RuntimeOrigin::signed(ALICE), 2),
+			Error::<Test>::Underflow
+		);
+		assert_ok!(Template::decrement(RuntimeOrigin::signed(ALICE), 1));
+		assert_eq!(Template::get_value(), Some(0));
+	});
+}
+
+#[test]
+fn decrement_requires_signed_origin() {
+	new_test_ext(Some(1)).execute_with(|| {
+		assert_noop!(Template::decrement(RuntimeOrigin::root(), 1), DispatchError::BadOrigin);
+	});
+}
+
+#[test]
+fn reset_works() {
+	new_test_ext(Some(3)).execute_with(|| {
+		assert_ok!(Template::reset(RuntimeOrigin::root()));
+		assert_eq!(Template::get_value(), None);
+		System::assert_last_event(Event::ValueChanged { old: Some(3), new: None, who: None }.into());
+	});
+}
+
+#[test]
+fn reset_requires_reset_origin() {
+	new_test_ext(Some(3)).execute_with(|| {
+		assert_noop!(Template::reset(RuntimeOrigin::signed(ALICE)), DispatchError::BadOrigin);
+	});
+}
+
+#[test]
+fn reset_fails_without_value() {
+	new_test_ext(None).execute_with(|| {
+		assert_noop!(Template::reset(RuntimeOrigin::root()), Error::<Test>::NoneValue);
+	});
+}
+
+#[test]
+fn failed_calls_emit_no_events() {
+	new_test_ext(None).execute_with(|| {
+		assert!(Template::increment(RuntimeOrigin::signed(ALICE), 1).is_err());
+		assert!(Template::reset(RuntimeOrigin::root()).is_err());
+		assert!(System::events().is_empty());
+	});
+}
+
+#[test]
+fn view_functions_work() {
+	new_test_ext(None).execute_with(|| {
+		assert_eq!(Template::get_value(), None);
+		assert!(!Template::is_set());
+
+		assert_ok!(Template::set_value(RuntimeOrigin::signed(ALICE), 5));
+		assert_eq!(Template::get_value(), Some(5));
+		assert!(Template::is_set());
+	});
+}
+
+#[test]
+fn normalize_value_task_restores_max_value() {
+	new_test_ext(Some(MAX_VALUE + 1)).execute_with(|| {
+		let task: RuntimeTask = Task::<Test>::NormalizeValue { current: MAX_VALUE + 1 }.into();
+		assert!(task.is_valid());
+
+		assert_ok!(System::do_task(RuntimeOrigin::signed(ALICE), task));
+		assert_eq!(Template::get_value(), Some(MAX_VALUE));
+		System::assert_has_event(
+			Event::ValueChanged { old: Some(MAX_VALUE + 1), new: Some(MAX_VALUE), who: None }
+				.into(),
+		);
+	});
+}
+
+#[test]
+fn normalize_value_task_requires_value_above_max() {
+	new_test_ext(Some(MAX_VALUE)).execute_with(|| {
+		let task: RuntimeTask = Task::<Test>::NormalizeValue { current: MAX_VALUE }.into();
+		assert!(!task.is_valid());
+		assert_noop!(
+			System::do_task(RuntimeOrigin::signed(ALICE), task),
+			frame_system::Error::<Test>::InvalidTask
+		);
+	});
+}
+
+#[test]
+fn normalize_value_task_rejects_stale_value() {
+	new_test_ext(Some(MAX_VALUE + 2)).execute_with(|| {
+		let task: RuntimeTask = Task::<Test>::NormalizeValue { current: MAX_VALUE + 1 }.into();
+		assert!(!task.is_valid());
+	});
+}
+
+#[test]
+fn offchain_worker_submits_normalize_value_task() {
+	let (mut ext, transactions) = new_offchain_test_ext(Some(MAX_VALUE + 1));
+
+	ext.execute_with(|| {
+		Template::offchain_worker(1);
+
+		let submitted = transactions();
+		let [tx] = &submitted[..] else { panic!("expected one submitted task") };
+		let tx = MockUncheckedExtrinsic::<Test>::decode(&mut &tx[..]).unwrap();
+		let task = Task::<Test>::NormalizeValue { current: MAX_VALUE + 1 }.into();
+		assert_eq!(tx.function, RuntimeCall::System(frame_system::Call::do_task { task }));
+	});
+}
+
+#[test]
+fn offchain_worker_does_not_resubmit_pending_task() {
+	let (mut ext, transactions) = new_offchain_test_ext(Some(MAX_VALUE + 1));
+
+	ext.execute_with(|| {
+		Template::offchain_worker(1);
+		Template::offchain_worker(2);
+		assert_eq!(transactions().len(), 1);
+
+		// 等待的区块数用完后任务仍未执行，重新提交
+		Template::offchain_worker(6);
+		assert_eq!(transactions().len(), 2);
+	});
+}
+
+#[test]
+fn offchain_worker_skips_value_within_bounds() {
+	let (mut ext, transactions) = new_offchain_test_ext(Some(MAX_VALUE));
+
+	ext.execute_with(|| {
+		Template::offchain_worker(1);
+		assert!(transactions().is_empty());
+	});
+}
+
+#[test]
+fn migration_v0_to_v1_preserves_value() {
+	new_test_ext(None).execute_with(|| {
+		StorageVersion::new(0).put::<Template>();
+		migrations::v1::v0::Value::<Test>::put(7);
+
+		migrations::v1::MigrateV0ToV1::<Test>::on_runtime_upgrade();
+
+		assert_eq!(Value::<Test>::get(), Some(ValueInfo { value: 7, setter: None, updated_at: 1 }));
+		assert_eq!(Template::on_chain_storage_version(), StorageVersion::new(1));
+	});
+}
+
+#[test]
+fn migration_v0_to_v1_handles_missing_value() {
+	new_test_ext(None).execute_with(|| {
+		StorageVersion::new(0).put::<Template>();
+
+		migrations::v1::MigrateV0ToV1::<Test>::on_runtime_upgrade();
+
+		assert_eq!(Value::<Test>::get(), None);
+		assert_eq!(Template::on_chain_storage_version(), StorageVersion::new(1));
+	});
+}
+
+#[test]
+fn migration_v0_to_v1_skips_when_already_migrated() {
+	new_test_ext(Some(5)).execute_with(|| {
+		assert_eq!(Template::on_chain_storage_version(), StorageVersion::new(1));
+
+		migrations::v1::MigrateV0ToV1::<Test>::on_runtime_upgrade();
+
+		assert_eq!(Value::<Test>::get(), Some(ValueInfo { value: 5, setter: None, updated_at: 0 }));
+	});
+}
+
+/// `who` 为账户值保留的押金
+fn held_deposit(who: u64) -> u64 {
+	Balances::balance_on_hold(&HoldReason::ValueDeposit.into(), &who)
+}
+
+#[test]
+fn claim_works() {
+	new_test_ext(None).execute_with(|| {
+		assert_ok!(Template::claim(RuntimeOrigin::signed(ALICE), 10));
+		assert_eq!(AccountValues::<Test>::get(ALICE), Some(10));
+		assert_eq!(held_deposit(ALICE), VALUE_DEPOSIT);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE - VALUE_DEPOSIT);
+		System::assert_last_event(
+			Event::ValueClaimed { who: ALICE, value: 10, deposit: VALUE_DEPOSIT }.into(),
+		);
+	});
+}
+
+#[test]
+fn claim_fails_when_already_claimed() {
+	new_test_ext(None).execute_with(|| {
+		assert_ok!(Template::claim(RuntimeOrigin::signed(ALICE), 10));
+		assert_noop!(
+			Template::claim(RuntimeOrigin::signed(ALICE), 11),
+			Error::<Test>::AlreadyClaimed
+		);
+	});
+}
+
+#[test]
+fn claim_fails_without_funds_for_deposit() {
+	new_test_ext(None).execute_with(|| {
+		// 账户 4 没有任何余额
+		assert!(Template::claim(RuntimeOrigin::signed(4), 10).is_err());
+		assert_eq!(AccountValues::<Test>::get(4), None);
+	});
+}
+
+#[test]
+fn update_works() {
+	new_test_ext(None).execute_with(|| {
+		assert_ok!(Template::claim(RuntimeOrigin::signed(ALICE), 10));
+		assert_ok!(Template::update(RuntimeOrigin::signed(ALICE), 20));
+		assert_eq!(AccountValues::<Test>::get(ALICE), Some(20));
+		assert_eq!(held_deposit(ALICE), VALUE_DEPOSIT);
+		System::assert_last_event(
+			Event::AccountValueUpdated { who: ALICE, old: 10, new: 20 }.into(),
+		);
+	});
+}
+
+#[test]
+fn update_fails_when_not_claimed() {
+	new_test_ext(None).execute_with(|| {
+		assert_noop!(Template::update(RuntimeOrigin::signed(ALICE), 20), Error::<Test>::NotClaimed);
+	});
+}
+
+#[test]
+fn transfer_ownership_moves_value_and_deposit() {
+	new_test_ext(None).execute_with(|| {
+		assert_ok!(Template::claim(RuntimeOrigin::signed(ALICE), 10));
+		assert_ok!(Template::transfer_ownership(RuntimeOrigin::signed(ALICE), BOB));
+
+		assert_eq!(AccountValues::<Test>::get(ALICE), None);
+		assert_eq!(AccountValues::<Test>::get(BOB), Some(10));
+		assert_eq!(held_deposit(ALICE), 0);
+		assert_eq!(held_deposit(BOB), VALUE_DEPOSIT);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE - VALUE_DEPOSIT);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE);
+		System::assert_last_event(
+			Event::OwnershipTransferred { from: ALICE, to: BOB, value: 10 }.into(),
+		);
+	});
+}
+
+#[test]
+fn transfer_ownership_fails_when_not_claimed() {
+	new_test_ext(None).execute_with(|| {
+		assert_noop!(
+			Template::transfer_ownership(RuntimeOrigin::signed(ALICE), BOB),
+			Error::<Test>::NotClaimed
+		);
+	});
+}
+
+#[test]
+fn transfer_ownership_fails_when_target_already_claimed() {
+	new_test_ext(None).execute_with(|| {
+		assert_ok!(Template::claim(RuntimeOrigin::signed(ALICE), 10));
+		assert_ok!(Template::claim(RuntimeOrigin::signed(BOB), 20));
+		assert_noop!(
+			Template::transfer_ownership(RuntimeOrigin::signed(ALICE), BOB),
+			Error::<Test>::AlreadyClaimed
+		);
+	});
+}
+
+#[test]
+fn transfer_ownership_fails_to_self() {
+	new_test_ext(None).execute_with(|| {
+		assert_ok!(Template::claim(RuntimeOrigin::signed(ALICE), 10));
+		assert_noop!(
+			Template::transfer_ownership(RuntimeOrigin::signed(ALICE), ALICE),
+			Error::<Test>::TransferToSelf
+		);
+	});
+}
+
+#[test]
+fn release_returns_deposit() {
+	new_test_ext(None).execute_with(|| {
+		assert_ok!(Template::claim(RuntimeOrigin::signed(ALICE), 10));
+		assert_ok!(Template::transfer_ownership(RuntimeOrigin::signed(ALICE), CHARLIE));
+		assert_ok!(Template::release(RuntimeOrigin::signed(CHARLIE)));
+
+		assert_eq!(AccountValues::<Test>::get(CHARLIE), None);
+		assert_eq!(held_deposit(CHARLIE), 0);
+		assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE + VALUE_DEPOSIT);
+		System::assert_last_event(
+			Event::ValueReleased { who: CHARLIE, value: 10, deposit: VALUE_DEPOSIT }.into(),
+		);
+	});
+}
+
+#[test]
+fn release_fails_when_not_claimed() {
+	new_test_ext(None).execute_with(|| {
+		assert_noop!(Template::release(RuntimeOrigin::signed(ALICE)), Error::<Test>::NotClaimed);
+	});
+}
+
+#[test]
+fn get_account_value_view_function_works() {
+	new_test_ext(None).execute_with(|| {
+		assert_eq!(Template::get_account_value(ALICE), None);
+		assert_ok!(Template::claim(RuntimeOrigin::signed(ALICE), 10));
+		assert_eq!(Template::get_account_value(ALICE), Some(10));
+	});
+}
+
+/// 模拟区块 `n` 的完成：设置时间戳并调用 `on_finalize`
+fn finalize_block(n: u64, now: u64) {
+	System::set_block_number(n);
+	Timestamp::set_timestamp(now);
+	Template::on_finalize(n);
+}
+
+#[test]
+fn on_initialize_reserves_record_weight() {
+	new_test_ext(None).execute_with(|| {
+		assert_eq!(
+			Template::on_initialize(1),
+			<() as crate::WeightInfo>::record_timestamp()
+		);
+	});
+}
+
+#[test]
+fn on_finalize_records_timestamp() {
+	new_test_ext(None).execute_with(|| {
+		finalize_block(1, 6_000);
+		finalize_block(2, 12_000);
+		assert_eq!(History::<Test>::get().into_inner(), vec![(1, 6_000), (2, 12_000)]);
+	});
+}
+
+#[test]
+fn history_drops_oldest_entry_when_full() {
+	new_test_ext(None).execute_with(|| {
+		for n in 1..=(MAX_HISTORY as u64 + 2) {
+			finalize_block(n, n * 1_000);
+		}
+		assert_eq!(
+			History::<Test>::get().into_inner(),
+			vec![(3, 3_000), (4, 4_000), (5, 5_000)]
+		);
+	});
+}
+
+#[test]
+fn on_idle_prunes_stale_entries() {
+	new_test_ext(None).execute_with(|| {
+		for n in 1..=3 {
+			finalize_block(n, n * 1_000);
+		}
+
+		// 区块 4 时，早于区块 4 - HISTORY_RETENTION = 2 的记录已过期
+		let used = Template::on_idle(4, Weight::MAX);
+		assert_eq!(used, <() as crate::WeightInfo>::prune_history());
+		assert_eq!(History::<Test>::get().into_inner(), vec![(2, 2_000), (3, 3_000)]);
+
+		// 之后每个区块先清理一条过期记录再追加新记录，记录不会在过期之前被覆盖
+		finalize_block(4, 4_000);
+		Template::on_idle(5, Weight::MAX);
+		finalize_block(5, 5_000);
+		assert_eq!(History::<Test>::get().into_inner(), vec![(3, 3_000), (4, 4_000), (5, 5_000)]);
+	});
+}
+
+#[test]
+fn on_idle_skips_pruning_without_enough_weight() {
+	new_test_ext(None).execute_with(|| {
+		finalize_block(1, 1_000);
+
+		assert_eq!(Template::on_idle(100, Weight::zero()), Weight::zero());
+		assert_eq!(History::<Test>::get().len(), 1);
+	});
+}
+
+#[test]
+fn integrity_test_passes() {
+	new_test_ext(None).execute_with(|| {
+		Template::integrity_test();
+	});
+}
+
+#[test]
+fn try_state_passes_after_calls() {
+	new_test_ext(Some(1)).execute_with(|| {
+		assert_ok!(Template::increment(RuntimeOrigin::signed(ALICE), 1));
+		assert_ok!(Template::claim(RuntimeOrigin::signed(ALICE), 10));
+		assert_ok!(Template::transfer_ownership(RuntimeOrigin::signed(ALICE), BOB));
+		finalize_block(1, 1_000);
+		finalize_block(2, 2_000);
+
+		assert_ok!(Template::do_try_state());
+	});
+}
+
+#[test]
+fn try_state_detects_value_from_future_block() {
+	new_test_ext(None).execute_with(|| {
+		Value::<Test>::put(ValueInfo { value: 1, setter: None, updated_at: 10 });
+		assert!(Template::do_try_state().is_err());
+	});
+}
+
+#[test]
+fn try_state_allows_value_above_max() {
+	new_test_ext(Some(MAX_VALUE)).execute_with(|| {
+		// 超出上限的值是合法的中间状态，由 `normalize_value` 任务在之后的区块中恢复
+		assert_ok!(Template::increment(RuntimeOrigin::signed(ALICE), 1));
+		assert_eq!(Template::get_value(), Some(MAX_VALUE + 1));
+		assert_ok!(Template::do_try_state());
+	});
+}
+
+#[test]
+fn try_state_detects_unsorted_history() {
+	new_test_ext(None).execute_with(|| {
+		System::set_block_number(5);
+		History::<Test>::put(BoundedVec::truncate_from(vec![(2, 2_000), (1, 1_000)]));
+		assert!(Template::do_try_state().is_err());
+	});
+}
+
+#[test]
+fn try_state_detects_missing_deposit() {
+	new_test_ext(None).execute_with(|| {
+		AccountValues::<Test>::insert(ALICE, 10);
+		assert!(Template::do_try_state().is_err());
+	});
+}
@@ -0,0 +1,180 @@
+//! `pallet_minimal_template` 的权重
+//!
+//! 当前数值按每个调用读写存储的次数估计。在参考硬件上执行
+//! `scripts/benchmark-weights.sh pallet_minimal_template` 会运行 `benchmarking.rs` 中的基准测试，
+//! 并用测量结果覆盖此文件。
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame::{deps::frame_support::weights::constants::RocksDbWeight, prelude::*};
+use polkadot_sdk::polkadot_sdk_frame as frame;
+use core::marker::PhantomData;
+
+/// Weight functions needed for `pallet_minimal_template`.
+pub trait WeightInfo {
+	fn set_value() -> Weight;
+	fn increment() -> Weight;
+	fn decrement() -> Weight;
+	fn reset() -> Weight;
+	fn normalize_value() -> Weight;
+	fn claim() -> Weight;
+	fn update() -> Weight;
+	fn transfer_ownership() -> Weight;
+	fn release() -> Weight;
+	fn record_timestamp() -> Weight;
+	fn prune_history() -> Weight;
+}
+
+/// `pallet_minimal_template` 的估计权重，`proof_size` 按存储项的最大编码长度估计
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// 读取 `Template::Value` 1 次，写入 1 次
+	fn set_value() -> Weight {
+		Weight::from_parts(6_000_000, 1526)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// 读取 `Template::Value` 1 次，写入 1 次
+	fn increment() -> Weight {
+		Weight::from_parts(7_000_000, 1526)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// 读取 `Template::Value` 1 次，写入 1 次
+	fn decrement() -> Weight {
+		Weight::from_parts(7_000_000, 1526)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// 读取 `Template::Value` 1 次，写入 1 次
+	fn reset() -> Weight {
+		Weight::from_parts(6_000_000, 1526)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// 读取 `Template::Value` 1 次，写入 1 次
+	fn normalize_value() -> Weight {
+		Weight::from_parts(8_000_000, 1526)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// 读取 `Template::AccountValues` 1 次，写入 1 次
+	/// 读取 `Balances::Holds` 1 次，写入 1 次
+	fn claim() -> Weight {
+		Weight::from_parts(32_000_000, 3550)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// 读取 `Template::AccountValues` 1 次，写入 1 次
+	fn update() -> Weight {
+		Weight::from_parts(10_000_000, 3517)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// 读取 `Template::AccountValues` 2 次，写入 2 次
+	/// 读取 `Balances::Holds` 2 次，写入 2 次
+	/// 读取 `System::Account` 1 次，写入 1 次
+	fn transfer_ownership() -> Weight {
+		Weight::from_parts(58_000_000, 6110)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// 读取 `Template::AccountValues` 1 次，写入 1 次
+	/// 读取 `Balances::Holds` 1 次，写入 1 次
+	fn release() -> Weight {
+		Weight::from_parts(30_000_000, 3550)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// 读取 `Template::History` 1 次，写入 1 次
+	/// 读取 `Timestamp::Now` 1 次
+	fn record_timestamp() -> Weight {
+		Weight::from_parts(9_000_000, 2687)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// 读取 `Template::History` 1 次，写入 1 次
+	fn prune_history() -> Weight {
+		Weight::from_parts(12_000_000, 2687)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+	/// 读取 `Template::Value` 1 次，写入 1 次
+	fn set_value() -> Weight {
+		Weight::from_parts(6_000_000, 1526)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// 读取 `Template::Value` 1 次，写入 1 次
+	fn increment() -> Weight {
+		Weight::from_parts(7_000_000, 1526)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// 读取 `Template::Value` 1 次，写入 1 次
+	fn decrement() -> Weight {
+		Weight::from_parts(7_000_000, 1526)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// 读取 `Template::Value` 1 次，写入 1 次
+	fn reset() -> Weight {
+		Weight::from_parts(6_000_000, 1526)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// 读取 `Template::Value` 1 次，写入 1 次
+	fn normalize_value() -> Weight {
+		Weight::from_parts(8_000_000, 1526)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// 读取 `Template::AccountValues` 1 次，写入 1 次
+	/// 读取 `Balances::Holds` 1 次，写入 1 次
+	fn claim() -> Weight {
+		Weight::from_parts(32_000_000, 3550)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// 读取 `Template::AccountValues` 1 次，写入 1 次
+	fn update() -> Weight {
+		Weight::from_parts(10_000_000, 3517)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// 读取 `Template::AccountValues` 2 次，写入 2 次
+	/// 读取 `Balances::Holds` 2 次，写入 2 次
+	/// 读取 `System::Account` 1 次，写入 1 次
+	fn transfer_ownership() -> Weight {
+		Weight::from_parts(58_000_000, 6110)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// 读取 `Template::AccountValues` 1 次，写入 1 次
+	/// 读取 `Balances::Holds` 1 次，写入 1 次
+	fn release() -> Weight {
+		Weight::from_parts(30_000_000, 3550)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// 读取 `Template::History` 1 次，写入 1 次
+	/// 读取 `Timestamp::Now` 1 次
+	fn record_timestamp() -> Weight {
+		Weight::from_parts(9_000_000, 2687)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// 读取 `Template::History` 1 次，写入 1 次
+	fn prune_history() -> Weight {
+		Weight::from_parts(12_000_000, 2687)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+}
@@ -28,14 +28,35 @@ include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
 extern crate alloc;
 
-use alloc::vec::Vec;
-use pallet_transaction_payment::{FeeDetails, RuntimeDispatchInfo};
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarks;
+
+use alloc::{vec, vec::Vec};
+use pallet_transaction_payment::{
+	FeeDetails, Multiplier, RuntimeDispatchInfo, TargetedFeeAdjustment,
+};
 use polkadot_sdk::{
+	frame_support::{
+		traits::{
+			fungible::{Balanced, Credit},
+			tokens::imbalance::Imbalance,
+			FindAuthor, OnTimestampSet, OnUnbalanced,
+		},
+		weights::{
+			constants::ExtrinsicBaseWeight, ConstantMultiplier, WeightToFeeCoefficient,
+			WeightToFeeCoefficients, WeightToFeePolynomial,
+		},
+	},
 	polkadot_sdk_frame::{
 		self as frame,
 		deps::sp_genesis_builder,
 		runtime::{apis, prelude::*},
 	},
+	sp_core::U256,
+	sp_runtime::{
+		traits::{Bounded, NumberFor},
+		ConsensusEngineId, FixedPointNumber, Perbill, Perquintill,
+	},
 	*,
 };
 
@@ -44,17 +65,20 @@ use polkadot_sdk::{
 pub mod genesis_config_presets {
 	use super::*;
 	use crate::{
-		interface::{Balance, MinimumBalance},
-		sp_keyring::Sr25519Keyring,
-		BalancesConfig, RuntimeGenesisConfig, SudoConfig,
+		interface::{AuraId, BabeId, Balance, GrandpaId, MinimumBalance},
+		sp_keyring::{Ed25519Keyring, Sr25519Keyring},
+		AuraConfig, BabeConfig, BalancesConfig, DifficultyConfig, GrandpaConfig,
+		RuntimeGenesisConfig, SudoConfig, TemplateConfig, BABE_GENESIS_EPOCH_CONFIG,
+		INITIAL_POW_DIFFICULTY,
 	};
 
 	use alloc::{vec, vec::Vec};
 	use serde_json::Value;
 
-	/// 返回开发环境的创世配置预设
-	/// 为所有测试账户预充值，并设置 Alice 为 sudo 账户
-	pub fn development_config_genesis() -> Value {
+	/// 构建测试网络的创世配置补丁
+	/// 为所有测试账户预充值，设置 Alice 为 sudo 账户，并以 `authorities` 作为验证人：
+	/// sr25519 密钥用于 Aura/BABE 出块，ed25519 密钥用于 GRANDPA 投票
+	fn testnet_genesis(authorities: Vec<(Sr25519Keyring, Ed25519Keyring)>) -> Value {
 		// 计算预充值金额：最小余额的 1000 倍，至少为 1
 		let endowment = <MinimumBalance as Get<Balance>>::get().max(1) * 1000;
 		frame_support::build_struct_json_patch!(RuntimeGenesisConfig {
@@ -66,13 +90,53 @@ pub mod genesis_config_presets {
 			},
 			// 设置 Alice 为 sudo（超级管理员）账户
 			sudo: SudoConfig { key: Some(Sr25519Keyring::Alice.to_account_id()) },
+			// 在 `--consensus aura` 模式下轮流出块的验证人
+			aura: AuraConfig {
+				authorities: authorities
+					.iter()
+					.map(|(aura, _)| aura.public().into())
+					.collect::<Vec<AuraId>>(),
+			},
+			// 在 `--consensus babe` 模式下通过 VRF 竞争 slot 的出块者，权重相同
+			babe: BabeConfig {
+				authorities: authorities
+					.iter()
+					.map(|(babe, _)| (babe.public().into(), 1))
+					.collect::<Vec<(BabeId, u64)>>(),
+				epoch_config: BABE_GENESIS_EPOCH_CONFIG,
+			},
+			// 在 `--finality grandpa` 模式下参与投票的验证人，权重相同
+			grandpa: GrandpaConfig {
+				authorities: authorities
+					.iter()
+					.map(|(_, grandpa)| (grandpa.public().into(), 1))
+					.collect::<Vec<(GrandpaId, u64)>>(),
+			},
+			// 为模板 pallet 设置初始值，使 `increment`/`decrement` 在开发链上可以直接使用
+			template: TemplateConfig { initial_value: Some(0) },
+			// `--consensus pow` 模式下的初始难度
+			difficulty: DifficultyConfig { initial_difficulty: INITIAL_POW_DIFFICULTY.into() },
 		})
 	}
 
+	/// 返回开发环境的创世配置预设，只有 Alice 一个出块者
+	pub fn development_config_genesis() -> Value {
+		testnet_genesis(vec![(Sr25519Keyring::Alice, Ed25519Keyring::Alice)])
+	}
+
+	/// 返回本地测试网络的创世配置预设，由 Alice 和 Bob 轮流出块并投票
+	pub fn local_testnet_genesis() -> Value {
+		testnet_genesis(vec![
+			(Sr25519Keyring::Alice, Ed25519Keyring::Alice),
+			(Sr25519Keyring::Bob, Ed25519Keyring::Bob),
+		])
+	}
+
 	/// Get the set of the available genesis config presets.
 	pub fn get_preset(id: &PresetId) -> Option<Vec<u8>> {
 		let patch = match id.as_ref() {
 			sp_genesis_builder::DEV_RUNTIME_PRESET => development_config_genesis(),
+			sp_genesis_builder::LOCAL_TESTNET_RUNTIME_PRESET => local_testnet_genesis(),
 			_ => return None,
 		};
 		Some(
@@ -84,7 +148,10 @@ pub mod genesis_config_presets {
 
 	/// List of supported presets.
 	pub fn preset_names() -> Vec<PresetId> {
-		vec![PresetId::from(sp_genesis_builder::DEV_RUNTIME_PRESET)]
+		vec![
+			PresetId::from(sp_genesis_builder::DEV_RUNTIME_PRESET),
+			PresetId::from(sp_genesis_builder::LOCAL_TESTNET_RUNTIME_PRESET),
+		]
 	}
 }
 
@@ -95,7 +162,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: alloc::borrow::Cow::Borrowed("minimal-template-runtime"),  // 规范名称
 	impl_name: alloc::borrow::Cow::Borrowed("minimal-template-runtime"),  // 实现名称
 	authoring_version: 1,      // 出块版本（影响出块者兼容性）
-	spec_version: 0,           // 规范版本（影响链规范兼容性）
+	spec_version: 7,           // 规范版本（每次修改运行时逻辑都要递增，否则升级后不会执行迁移）
 	impl_version: 1,           // 实现版本（用于区分实现）
 	apis: RUNTIME_API_VERSIONS, // 运行时 API 版本
 	transaction_version: 1,     // 交易版本（影响交易格式）
@@ -183,10 +250,137 @@ mod runtime {
 	/// 一个最小化的 pallet 模板，作为自定义 pallet 的起点
 	#[runtime::pallet_index(5)]
 	pub type Template = pallet_minimal_template::Pallet<Runtime>;
+
+	/// 出块者 pallet（索引 6）
+	/// 记录当前区块的作者，用于分配交易费用
+	#[runtime::pallet_index(6)]
+	pub type Authorship = pallet_authorship::Pallet<Runtime>;
+
+	/// Aura pallet（索引 7）
+	/// 记录出块者集合和当前 slot，供 `--consensus aura` 模式轮流出块
+	#[runtime::pallet_index(7)]
+	pub type Aura = pallet_aura::Pallet<Runtime>;
+
+	/// GRANDPA pallet（索引 8）
+	/// 记录最终性投票者集合，供 `--finality grandpa` 模式使用
+	#[runtime::pallet_index(8)]
+	pub type Grandpa = pallet_grandpa::Pallet<Runtime>;
+
+	/// BABE pallet（索引 9）
+	/// 记录纪元、出块者集合和 VRF 随机数，供 `--consensus babe` 模式使用
+	#[runtime::pallet_index(9)]
+	pub type Babe = pallet_babe::Pallet<Runtime>;
+
+	/// 难度 pallet（索引 10）
+	/// 记录工作量证明的难度，供 `--consensus pow` 模式使用
+	#[runtime::pallet_index(10)]
+	pub type Difficulty = pallet_difficulty::Pallet<Runtime>;
 }
 
+/// 链上余额类型
+/// 与之前的默认配置一致，保持 `System::Account` 等存储的编码不变，已有的链可以直接升级
+pub type Balance = u64;
+
+/// 一个完整的代币单位（12 位小数）
+pub const UNIT: Balance = 1_000_000_000_000;
+/// 千分之一个代币单位
+pub const MILLI_UNIT: Balance = UNIT / 1_000;
+/// 百万分之一个代币单位
+pub const MICRO_UNIT: Balance = MILLI_UNIT / 1_000;
+
+/// 账户存在所需的最小余额
+pub const EXISTENTIAL_DEPOSIT: Balance = MILLI_UNIT;
+
+/// 目标出块时间（毫秒），需要与节点的出块间隔保持一致
+pub const MILLI_SECS_PER_BLOCK: u64 = 3000;
+
+/// BABE 纪元长度（slot 数），即 10 分钟
+pub const EPOCH_DURATION_IN_SLOTS: u64 = 10 * 60_000 / MILLI_SECS_PER_BLOCK;
+
+/// BABE 主 slot 的分配概率：每个 slot 平均有 1/4 的出块者获得主 slot
+pub const PRIMARY_PROBABILITY: (u64, u64) = (1, 4);
+
+/// 创世时的 BABE 纪元配置
+/// 没有出块者获得主 slot 时，由按轮次确定的次级出块者（附带 VRF 输出）补位
+pub const BABE_GENESIS_EPOCH_CONFIG: sp_consensus_babe::BabeEpochConfiguration =
+	sp_consensus_babe::BabeEpochConfiguration {
+		c: PRIMARY_PROBABILITY,
+		allowed_slots: sp_consensus_babe::AllowedSlots::PrimaryAndSecondaryVRFSlots,
+	};
+
+/// 创世时的工作量证明难度
+/// 平均需要尝试这么多次哈希才能找到一个有效的 nonce。按单个线程每秒约 400 万次哈希估算，
+/// 平均约 4 秒出一个块，高于 [`MinimumPeriod`]；运气好提前挖出的区块由节点把时间戳调整到父区块加上该间隔
+pub const INITIAL_POW_DIFFICULTY: u64 = 16_000_000;
+
+/// 一笔基础外部调用（不含调用本身的执行权重）的目标费用
+pub const BASE_EXTRINSIC_FEE: Balance = MILLI_UNIT / 10;
+
 parameter_types! {
 	pub const Version: RuntimeVersion = VERSION;
+	pub const ExistentialDeposit: Balance = EXISTENTIAL_DEPOSIT;
+	pub const EpochDuration: u64 = EPOCH_DURATION_IN_SLOTS;
+	pub const ExpectedBlockTime: u64 = MILLI_SECS_PER_BLOCK;
+	/// 两个区块之间的最小时间间隔为出块时间的一半，BABE 的 slot 时长为它的两倍
+	/// 手动出块和即时出块模式下，节点会把时间戳调整为不早于父区块加上该间隔
+	pub const MinimumPeriod: u64 = MILLI_SECS_PER_BLOCK / 2;
+	/// 在模板 pallet 中认领账户值时保留的押金
+	pub const TemplateValueDeposit: Balance = 10 * MILLI_UNIT;
+
+	/// 交易每个字节收取的费用
+	pub const TransactionByteFee: Balance = MICRO_UNIT;
+	/// 操作类交易优先级的小费倍数
+	pub const OperationalFeeMultiplier: u8 = 5;
+	/// 区块的目标满载率，高于该值时费用上涨，低于该值时费用下降
+	pub const TargetBlockFullness: Perquintill = Perquintill::from_percent(25);
+	/// 费用乘数的调整速度
+	pub AdjustmentVariable: Multiplier = Multiplier::saturating_from_rational(75, 1_000_000);
+	/// 费用乘数的下限，避免空闲时费用趋近于零
+	pub MinimumMultiplier: Multiplier = Multiplier::saturating_from_rational(1, 10u128);
+	pub MaximumMultiplier: Multiplier = Bounded::max_value();
+
+	/// 工作量证明难度的下限
+	pub MinDifficulty: U256 = U256::from(1_000);
+}
+
+/// 将权重转换为费用
+/// 线性多项式，使 [`ExtrinsicBaseWeight`] 恰好对应 [`BASE_EXTRINSIC_FEE`]
+pub struct WeightToFee;
+impl WeightToFeePolynomial for WeightToFee {
+	type Balance = Balance;
+
+	fn polynomial() -> WeightToFeeCoefficients<Self::Balance> {
+		let p = BASE_EXTRINSIC_FEE;
+		let q: Balance = ExtrinsicBaseWeight::get().ref_time();
+		vec![WeightToFeeCoefficient {
+			degree: 1,
+			negative: false,
+			coeff_frac: Perbill::from_rational(p % q, q),
+			coeff_integer: p / q,
+		}]
+		.into()
+	}
+}
+
+/// 交易费用的分配
+/// 80% 的手续费被销毁，剩余 20% 连同全部小费奖励给区块作者；
+/// 无法确定区块作者时全部销毁
+pub struct DealWithFees;
+impl OnUnbalanced<Credit<interface::AccountId, Balances>> for DealWithFees {
+	fn on_unbalanceds(
+		mut fees_then_tips: impl Iterator<Item = Credit<interface::AccountId, Balances>>,
+	) {
+		if let Some(fees) = fees_then_tips.next() {
+			// 丢弃的 credit 会减少总发行量，即被销毁
+			let (_to_burn, mut to_author) = fees.ration(80, 20);
+			if let Some(tips) = fees_then_tips.next() {
+				tips.merge_into(&mut to_author);
+			}
+			if let Some(author) = Authorship::author() {
+				let _ = Balances::resolve(&author, to_author);
+			}
+		}
+	}
 }
 
 /// 实现系统 pallet 所需的类型
@@ -196,41 +390,211 @@ impl frame_system::Config for Runtime {
 	type Block = Block;           // 区块类型
 	type Version = Version;       // 版本信息
 	// 使用余额 pallet 的账户数据
-	type AccountData = pallet_balances::AccountData<<Runtime as pallet_balances::Config>::Balance>;
+	type AccountData = pallet_balances::AccountData<Balance>;
 }
 
 // 实现余额 pallet 所需的类型
-#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
 impl pallet_balances::Config for Runtime {
-	type AccountStore = System;  // 使用系统 pallet 存储账户数据
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = Balance;
+	type DustRemoval = ();           // 清理的尘埃余额直接销毁
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;      // 使用系统 pallet 存储账户数据
+	type WeightInfo = pallet_balances::weights::SubstrateWeight<Runtime>;
+	// 每个账户最多 50 个锁，不再使用已弃用的 reserve
+	type MaxLocks = ConstU32<50>;
+	type MaxReserves = ConstU32<0>;
+	type ReserveIdentifier = [u8; 8];
+	// 使用运行时聚合的保留/冻结原因，使其他 pallet 可以通过余额 pallet 保留或冻结资金
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type RuntimeFreezeReason = RuntimeFreezeReason;
+	type FreezeIdentifier = RuntimeFreezeReason;
+	type MaxFreezes = frame_support::traits::VariantCountOf<RuntimeFreezeReason>;
+	type DoneSlashHandler = ();
 }
 
 // 实现 sudo pallet 所需的类型
-#[derive_impl(pallet_sudo::config_preludes::TestDefaultConfig)]
-impl pallet_sudo::Config for Runtime {}
+impl pallet_sudo::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type WeightInfo = pallet_sudo::weights::SubstrateWeight<Runtime>;
+}
 
 // 实现时间戳 pallet 所需的类型
-#[derive_impl(pallet_timestamp::config_preludes::TestDefaultConfig)]
-impl pallet_timestamp::Config for Runtime {}
+impl pallet_timestamp::Config for Runtime {
+	type Moment = u64;                // 以毫秒为单位的 Unix 时间
+	// 由区块使用的 Aura 或 BABE 校验时间戳与 slot 一致，手动出块、即时出块和 PoW 的区块不做校验
+	type OnTimestampSet = SlotTimestampCheck;
+	type MinimumPeriod = MinimumPeriod;
+	type WeightInfo = pallet_timestamp::weights::SubstrateWeight<Runtime>;
+}
 
 // 实现交易支付 pallet 所需的类型
-#[derive_impl(pallet_transaction_payment::config_preludes::TestDefaultConfig)]
 impl pallet_transaction_payment::Config for Runtime {
-	type OnChargeTransaction = pallet_transaction_payment::FungibleAdapter<Balances, ()>;
-	// 为了演示目的，将费用设置为与外部调用的权重无关（免费）
-	type WeightToFee = NoFee<<Self as pallet_balances::Config>::Balance>;
-	// 为了演示目的，将费用设置为固定值，与调用数据长度无关
-	type LengthToFee = FixedFee<1, <Self as pallet_balances::Config>::Balance>;
+	type RuntimeEvent = RuntimeEvent;
+	// 从发送者账户扣除费用，并按 [`DealWithFees`] 分配
+	type OnChargeTransaction = pallet_transaction_payment::FungibleAdapter<Balances, DealWithFees>;
+	// 费用由调用权重和交易长度共同决定
+	type WeightToFee = WeightToFee;
+	type LengthToFee = ConstantMultiplier<Balance, TransactionByteFee>;
+	// 根据区块满载率动态调整费用乘数
+	type FeeMultiplierUpdate = TargetedFeeAdjustment<
+		Self,
+		TargetBlockFullness,
+		AdjustmentVariable,
+		MinimumMultiplier,
+		MaximumMultiplier,
+	>;
+	type OperationalFeeMultiplier = OperationalFeeMultiplier;
+	type WeightInfo = pallet_transaction_payment::weights::SubstrateWeight<Runtime>;
+}
+
+// 实现出块者 pallet 所需的类型
+impl pallet_authorship::Config for Runtime {
+	// 从 Aura 或 BABE 的 pre-runtime 摘要中找到出块者；手动出块模式下没有作者
+	type FindAuthor = SlotAuthorAccountAdapter;
+	type EventHandler = ();
+}
+
+/// 将 Aura/BABE 出块者的 sr25519 公钥映射为同名账户
+pub struct SlotAuthorAccountAdapter;
+impl FindAuthor<interface::AccountId> for SlotAuthorAccountAdapter {
+	fn find_author<'a, I>(digests: I) -> Option<interface::AccountId>
+	where
+		I: 'a + IntoIterator<Item = (ConsensusEngineId, &'a [u8])>,
+	{
+		let digests: Vec<_> = digests.into_iter().collect();
+		let aura = || {
+			let id = pallet_aura::AuraAuthorId::<Runtime>::find_author(digests.iter().copied())?;
+			interface::AccountId::try_from(id.as_ref()).ok()
+		};
+		let babe = || {
+			let index = Babe::find_author(digests.iter().copied())?;
+			let (id, _) = pallet_babe::Authorities::<Runtime>::get().get(index as usize)?.clone();
+			interface::AccountId::try_from(id.as_ref()).ok()
+		};
+		aura().or_else(babe)
+	}
+}
+
+/// 将时间戳转发给出块时使用的 slot 共识 pallet
+/// 只有带 Aura 或 BABE pre-runtime 摘要的区块才有 slot，其他区块（手动出块、即时出块和 PoW）不做校验
+pub struct SlotTimestampCheck;
+impl OnTimestampSet<u64> for SlotTimestampCheck {
+	fn on_timestamp_set(moment: u64) {
+		let digest = System::digest();
+		let has_pre_digest = |id: ConsensusEngineId| {
+			digest
+				.logs()
+				.iter()
+				.any(|log| matches!(log.as_pre_runtime(), Some((engine, _)) if engine == id))
+		};
+		if has_pre_digest(sp_consensus_aura::AURA_ENGINE_ID) {
+			<Aura as OnTimestampSet<u64>>::on_timestamp_set(moment);
+		} else if has_pre_digest(sp_consensus_babe::BABE_ENGINE_ID) {
+			<Babe as OnTimestampSet<u64>>::on_timestamp_set(moment);
+		}
+	}
+}
+
+// 实现 Aura pallet 所需的类型
+impl pallet_aura::Config for Runtime {
+	type AuthorityId = interface::AuraId;
+	type DisabledValidators = ();
+	type MaxAuthorities = ConstU32<32>;
+	// 每个 slot 最多出一个块
+	type AllowMultipleBlocksPerSlot = frame_support::traits::ConstBool<false>;
+	// slot 时长与目标出块时间一致
+	type SlotDuration = ConstU64<MILLI_SECS_PER_BLOCK>;
+}
+
+// 实现 BABE pallet 所需的类型
+impl pallet_babe::Config for Runtime {
+	type EpochDuration = EpochDuration;
+	type ExpectedBlockTime = ExpectedBlockTime;
+	// 没有会话 pallet，出块者集合在创世时确定后保持不变
+	type EpochChangeTrigger = pallet_babe::SameAuthoritiesForever;
+	type DisabledValidators = ();
+	type WeightInfo = ();
+	type MaxAuthorities = ConstU32<32>;
+	type MaxNominators = ConstU32<0>;
+	// 没有会话 pallet，暂不支持举报出块者的双重签名
+	type KeyOwnerProof = sp_core::Void;
+	type EquivocationReportSystem = ();
+}
+
+// 实现 GRANDPA pallet 所需的类型
+impl pallet_grandpa::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = ();
+	type MaxAuthorities = ConstU32<32>;
+	type MaxNominators = ConstU32<0>;
+	type MaxSetIdSessionEntries = ConstU64<0>;
+	// 没有会话 pallet，暂不支持举报投票者的双重签名
+	type KeyOwnerProof = sp_core::Void;
+	type EquivocationReportSystem = ();
+}
+
+// 实现难度 pallet 所需的类型
+impl pallet_difficulty::Config for Runtime {
+	// 只有 root 可以调整难度
+	type DifficultyOrigin = frame_system::EnsureRoot<<Runtime as frame_system::Config>::AccountId>;
+	type MinDifficulty = MinDifficulty;
+	type WeightInfo = pallet_difficulty::weights::SubstrateWeight<Runtime>;
 }
 
 // 实现模板 pallet 所需的类型
-impl pallet_minimal_template::Config for Runtime {}
+impl pallet_minimal_template::Config for Runtime {
+	type RuntimeTask = RuntimeTask;
+	// 只有 root 可以清除模板 pallet 中的值
+	type ResetOrigin = frame_system::EnsureRoot<<Runtime as frame_system::Config>::AccountId>;
+	// 超出此上限的值会被链下工作机提交的任务恢复
+	type MaxValue = ConstU32<1_000_000>;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	// 账户值的押金通过余额 pallet 保留
+	type Currency = Balances;
+	type ValueDeposit = TemplateValueDeposit;
+	// 从时间戳 pallet 读取区块时间
+	type Time = Timestamp;
+	// 最多保存 100 个区块的时间戳，并在空闲时清理 50 个区块之前的记录
+	type MaxHistory = ConstU32<100>;
+	type HistoryRetention = ConstU32<50>;
+	type WeightInfo = pallet_minimal_template::weights::SubstrateWeight<Runtime>;
+}
+
+// 允许 pallet 通过链下工作机提交无签名交易（如运行时任务）
+impl<LocalCall> frame_system::offchain::CreateTransactionBase<LocalCall> for Runtime
+where
+	RuntimeCall: From<LocalCall>,
+{
+	type Extrinsic = ExtrinsicFor<Runtime>;
+	type RuntimeCall = RuntimeCall;
+}
+
+impl<LocalCall> frame_system::offchain::CreateBare<LocalCall> for Runtime
+where
+	RuntimeCall: From<LocalCall>,
+{
+	fn create_bare(call: RuntimeCall) -> ExtrinsicFor<Runtime> {
+		ExtrinsicFor::<Runtime>::new_bare(call)
+	}
+}
 
 type Block = frame::runtime::types_common::BlockOf<Runtime, TxExtension>;
 type Header = HeaderFor<Runtime>;
 
-type RuntimeExecutive =
-	Executive<Runtime, Block, frame_system::ChainContext<Runtime>, Runtime, AllPalletsWithSystem>;
+/// 运行时升级时需要执行的存储迁移
+/// 每个迁移都带有存储版本检查，已经执行过的迁移会被自动跳过
+type Migrations = (pallet_minimal_template::migrations::v1::MigrateV0ToV1<Runtime>,);
+
+type RuntimeExecutive = Executive<
+	Runtime,
+	Block,
+	frame_system::ChainContext<Runtime>,
+	Runtime,
+	AllPalletsWithSystem,
+	Migrations,
+>;
 
 impl_runtime_apis! {
 	impl apis::Core<Block> for Runtime {
@@ -298,14 +662,100 @@ impl_runtime_apis! {
 	}
 
 	impl apis::SessionKeys<Block> for Runtime {
-		fn generate_session_keys(_owner: Vec<u8>, _seed: Option<Vec<u8>>) -> apis::OpaqueGeneratedSessionKeys {
-			apis::OpaqueGeneratedSessionKeys { keys: Default::default(), proof: Default::default() }
+		fn generate_session_keys(owner: Vec<u8>, seed: Option<Vec<u8>>) -> apis::OpaqueGeneratedSessionKeys {
+			interface::SessionKeys::generate(&owner, seed).into()
 		}
 
 		fn decode_session_keys(
-			_encoded: Vec<u8>,
+			encoded: Vec<u8>,
 		) -> Option<Vec<(Vec<u8>, apis::KeyTypeId)>> {
-			Default::default()
+			interface::SessionKeys::decode_into_raw_public_keys(&encoded)
+		}
+	}
+
+	impl sp_consensus_grandpa::GrandpaApi<Block> for Runtime {
+		fn grandpa_authorities() -> sp_consensus_grandpa::AuthorityList {
+			Grandpa::grandpa_authorities()
+		}
+
+		fn current_set_id() -> sp_consensus_grandpa::SetId {
+			Grandpa::current_set_id()
+		}
+
+		fn submit_report_equivocation_unsigned_extrinsic(
+			_equivocation_proof: sp_consensus_grandpa::EquivocationProof<
+				<Block as frame::traits::Block>::Hash,
+				NumberFor<Block>,
+			>,
+			_key_owner_proof: sp_consensus_grandpa::OpaqueKeyOwnershipProof,
+		) -> Option<()> {
+			None
+		}
+
+		fn generate_key_ownership_proof(
+			_set_id: sp_consensus_grandpa::SetId,
+			_authority_id: interface::GrandpaId,
+		) -> Option<sp_consensus_grandpa::OpaqueKeyOwnershipProof> {
+			// 没有会话 pallet，无法证明密钥归属
+			None
+		}
+	}
+
+	impl sp_consensus_babe::BabeApi<Block> for Runtime {
+		fn configuration() -> sp_consensus_babe::BabeConfiguration {
+			let epoch_config =
+				pallet_babe::EpochConfig::<Runtime>::get().unwrap_or(BABE_GENESIS_EPOCH_CONFIG);
+			sp_consensus_babe::BabeConfiguration {
+				slot_duration: Babe::slot_duration(),
+				epoch_length: EpochDuration::get(),
+				c: epoch_config.c,
+				authorities: pallet_babe::Authorities::<Runtime>::get().to_vec(),
+				randomness: pallet_babe::Randomness::<Runtime>::get(),
+				allowed_slots: epoch_config.allowed_slots,
+			}
+		}
+
+		fn current_epoch_start() -> sp_consensus_babe::Slot {
+			Babe::current_epoch_start()
+		}
+
+		fn current_epoch() -> sp_consensus_babe::Epoch {
+			Babe::current_epoch()
+		}
+
+		fn next_epoch() -> sp_consensus_babe::Epoch {
+			Babe::next_epoch()
+		}
+
+		fn generate_key_ownership_proof(
+			_slot: sp_consensus_babe::Slot,
+			_authority_id: sp_consensus_babe::AuthorityId,
+		) -> Option<sp_consensus_babe::OpaqueKeyOwnershipProof> {
+			// 没有会话 pallet，无法证明密钥归属
+			None
+		}
+
+		fn submit_report_equivocation_unsigned_extrinsic(
+			_equivocation_proof: sp_consensus_babe::EquivocationProof<HeaderFor<Runtime>>,
+			_key_owner_proof: sp_consensus_babe::OpaqueKeyOwnershipProof,
+		) -> Option<()> {
+			None
+		}
+	}
+
+	impl sp_consensus_aura::AuraApi<Block, interface::AuraId> for Runtime {
+		fn slot_duration() -> sp_consensus_aura::SlotDuration {
+			sp_consensus_aura::SlotDuration::from_millis(Aura::slot_duration())
+		}
+
+		fn authorities() -> Vec<interface::AuraId> {
+			pallet_aura::Authorities::<Runtime>::get().into_inner()
+		}
+	}
+
+	impl sp_consensus_pow::DifficultyApi<Block, U256> for Runtime {
+		fn difficulty() -> U256 {
+			Difficulty::difficulty()
 		}
 	}
 
@@ -333,6 +783,78 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl frame_support::view_functions::runtime_api::RuntimeViewFunction<Block> for Runtime {
+		fn execute_view_function(
+			id: frame_support::view_functions::ViewFunctionId,
+			input: Vec<u8>,
+		) -> Result<Vec<u8>, frame_support::view_functions::ViewFunctionDispatchError> {
+			Runtime::execute_view_function(id, input)
+		}
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	impl frame_benchmarking::Benchmark<Block> for Runtime {
+		fn benchmark_metadata(extra: bool) -> (
+			Vec<frame_benchmarking::BenchmarkList>,
+			Vec<frame_support::traits::StorageInfo>,
+		) {
+			use frame_benchmarking::{baseline::Pallet as BaselineBench, BenchmarkList};
+			use frame_support::traits::StorageInfoTrait;
+			use frame_system_benchmarking::Pallet as SystemBench;
+
+			let mut list = Vec::<BenchmarkList>::new();
+			list_benchmarks!(list, extra);
+
+			let storage_info = AllPalletsWithSystem::storage_info();
+
+			(list, storage_info)
+		}
+
+		#[allow(non_local_definitions)]
+		fn dispatch_benchmark(
+			config: frame_benchmarking::BenchmarkConfig,
+		) -> Result<Vec<frame_benchmarking::BenchmarkBatch>, alloc::string::String> {
+			use frame_benchmarking::{
+				baseline::{self, Pallet as BaselineBench},
+				BenchmarkBatch, TrackedStorageKey,
+			};
+			use frame_support::traits::WhitelistedStorageKeys;
+			use frame_system_benchmarking::Pallet as SystemBench;
+
+			impl frame_system_benchmarking::Config for Runtime {}
+			impl baseline::Config for Runtime {}
+
+			// 白名单中的存储键（如区块号、事件计数）的读写不计入权重
+			let whitelist: Vec<TrackedStorageKey> =
+				AllPalletsWithSystem::whitelisted_storage_keys();
+
+			let mut batches = Vec::<BenchmarkBatch>::new();
+			let params = (&config, &whitelist);
+			add_benchmarks!(params, batches);
+
+			Ok(batches)
+		}
+	}
+
+	#[cfg(feature = "try-runtime")]
+	impl frame_try_runtime::TryRuntime<Block> for Runtime {
+		fn on_runtime_upgrade(checks: frame_try_runtime::UpgradeCheckSelect) -> (Weight, Weight) {
+			// 迁移执行失败时直接 panic，try-runtime CLI 会将其作为错误报告
+			let weight = RuntimeExecutive::try_runtime_upgrade(checks).unwrap();
+			(weight, <Runtime as frame_system::Config>::BlockWeights::get().max_block)
+		}
+
+		fn execute_block(
+			block: <Block as frame::traits::Block>::LazyBlock,
+			state_root_check: bool,
+			signature_check: bool,
+			select: frame_try_runtime::TryStateSelect,
+		) -> Weight {
+			RuntimeExecutive::try_execute_block(block, state_root_check, signature_check, select)
+				.expect("execute-block failed")
+		}
+	}
+
 	impl apis::GenesisBuilder<Block> for Runtime {
 		fn build_state(config: Vec<u8>) -> sp_genesis_builder::Result {
 			build_state::<RuntimeGenesisConfig>(config)
@@ -359,9 +881,23 @@ pub mod interface {
 
 	pub type Block = super::Block;
 	pub use frame::runtime::types_common::OpaqueBlock;
+	pub type TxExtension = super::TxExtension;
+	pub type UncheckedExtrinsic = super::ExtrinsicFor<Runtime>;
 	pub type AccountId = <Runtime as frame_system::Config>::AccountId;
 	pub type Nonce = <Runtime as frame_system::Config>::Nonce;
 	pub type Hash = <Runtime as frame_system::Config>::Hash;
-	pub type Balance = <Runtime as pallet_balances::Config>::Balance;
+	pub type Balance = super::Balance;
 	pub type MinimumBalance = <Runtime as pallet_balances::Config>::ExistentialDeposit;
+	pub type AuraId = sp_consensus_aura::sr25519::AuthorityId;
+	pub use sp_consensus_babe::AuthorityId as BabeId;
+	pub use sp_consensus_grandpa::AuthorityId as GrandpaId;
+
+	sp_runtime::impl_opaque_keys! {
+		/// 节点需要为出块生成并保存在密钥库中的会话密钥
+		pub struct SessionKeys {
+			pub aura: super::Aura,
+			pub babe: super::Babe,
+			pub grandpa: super::Grandpa,
+		}
+	}
 }
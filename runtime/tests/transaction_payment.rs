@@ -0,0 +1,123 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 针对 `TransactionPaymentApi` 的集成测试
+//!
+//! 在创世状态上调用运行时的 `TransactionPaymentApi` 实现，与节点的 `payment_queryInfo` /
+//! `payment_queryFeeDetails` RPC 走同一条路径，验证钱包通过 RPC 拿到的费用估算
+
+use codec::Encode;
+use minimal_template_runtime::{
+	interface::{Balance, Block, TxExtension, UncheckedExtrinsic},
+	Runtime, RuntimeCall, RuntimeGenesisConfig, TransactionByteFee, BASE_EXTRINSIC_FEE,
+};
+use polkadot_sdk::{
+	frame_support::{dispatch::GetDispatchInfo, traits::Get},
+	pallet_transaction_payment::{FeeDetails, RuntimeDispatchInfo},
+	pallet_transaction_payment_rpc_runtime_api::runtime_decl_for_transaction_payment_api::TransactionPaymentApiV4,
+	sp_io::TestExternalities,
+	sp_keyring::Sr25519Keyring,
+	sp_runtime::{generic::Era, BuildStorage, MultiAddress},
+	*,
+};
+
+fn new_test_ext() -> TestExternalities {
+	RuntimeGenesisConfig::default().build_storage().unwrap().into()
+}
+
+fn tx_extension() -> TxExtension {
+	(
+		frame_system::AuthorizeCall::new(),
+		frame_system::CheckNonZeroSender::new(),
+		frame_system::CheckSpecVersion::new(),
+		frame_system::CheckTxVersion::new(),
+		frame_system::CheckGenesis::new(),
+		frame_system::CheckEra::from(Era::Immortal),
+		frame_system::CheckNonce::from(0),
+		frame_system::CheckWeight::new(),
+		pallet_transaction_payment::ChargeTransactionPayment::from(0),
+		frame_system::WeightReclaim::new(),
+	)
+}
+
+/// 构造一笔由 Alice 签名的 `System::remark` 交易及其编码长度
+fn signed_remark(len: usize) -> (UncheckedExtrinsic, u32) {
+	let alice = Sr25519Keyring::Alice;
+	let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![0u8; len] });
+	// 费用查询不会校验签名，这里只需要一个格式正确的签名
+	let xt = UncheckedExtrinsic::new_signed(
+		call,
+		MultiAddress::Id(alice.to_account_id()),
+		alice.sign(b"fee estimation").into(),
+		tx_extension(),
+	);
+	let len = xt.encoded_size() as u32;
+	(xt, len)
+}
+
+/// 通过运行时 API 查询交易的权重、类别和费用
+fn query_info(xt: UncheckedExtrinsic, len: u32) -> RuntimeDispatchInfo<Balance> {
+	<Runtime as TransactionPaymentApiV4<Block, Balance>>::query_info(xt, len)
+}
+
+/// 通过运行时 API 查询交易费用的各个组成部分
+fn query_fee_details(xt: UncheckedExtrinsic, len: u32) -> FeeDetails<Balance> {
+	<Runtime as TransactionPaymentApiV4<Block, Balance>>::query_fee_details(xt, len)
+}
+
+#[test]
+fn query_info_reports_weight_and_fee() {
+	new_test_ext().execute_with(|| {
+		let (xt, len) = signed_remark(32);
+		let dispatch_info = xt.get_dispatch_info();
+
+		let info = query_info(xt, len);
+
+		assert_eq!(info.weight, dispatch_info.total_weight());
+		assert_eq!(info.class, dispatch_info.class);
+		assert!(info.partial_fee > BASE_EXTRINSIC_FEE);
+	});
+}
+
+#[test]
+fn query_fee_details_splits_fee_components() {
+	new_test_ext().execute_with(|| {
+		let (xt, len) = signed_remark(32);
+
+		let details = query_fee_details(xt.clone(), len);
+		let inclusion_fee = details.inclusion_fee.expect("signed extrinsics pay an inclusion fee");
+
+		// 基础费用按 `BASE_EXTRINSIC_FEE` 校准，只允许多项式系数带来的舍入误差
+		assert!(inclusion_fee.base_fee.abs_diff(BASE_EXTRINSIC_FEE) <= BASE_EXTRINSIC_FEE / 1_000);
+		assert_eq!(inclusion_fee.len_fee, Balance::from(len) * TransactionByteFee::get());
+		assert_eq!(details.tip, 0);
+		assert_eq!(details.final_fee(), query_info(xt, len).partial_fee);
+	});
+}
+
+#[test]
+fn longer_extrinsics_cost_more() {
+	new_test_ext().execute_with(|| {
+		let (short, short_len) = signed_remark(8);
+		let (long, long_len) = signed_remark(1_024);
+
+		let short_fee = query_info(short, short_len).partial_fee;
+		let long_fee = query_info(long, long_len).partial_fee;
+
+		assert!(long_fee > short_fee);
+	});
+}
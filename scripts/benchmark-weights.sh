@@ -0,0 +1,50 @@
+#!/bin/bash
+# pallet 权重生成脚本
+# 在参考硬件上执行，构建带基准测试的运行时并重新生成各 pallet 的 weights.rs
+#
+# 用法: scripts/benchmark-weights.sh [pallet_minimal_template|pallet_difficulty]
+# 不带参数时重新生成所有本地 pallet 的权重
+
+set -e
+
+cd "$(dirname "$0")/.."
+
+RUNTIME_WASM="target/release/wbuild/minimal-template-runtime/minimal_template_runtime.wasm"
+
+# pallet 名称与权重文件的对应关系
+declare -A WEIGHT_FILES=(
+    [pallet_minimal_template]="pallets/template/src/weights.rs"
+    [pallet_difficulty]="pallets/difficulty/src/weights.rs"
+)
+
+if [ $# -gt 0 ]; then
+    PALLETS=("$@")
+else
+    PALLETS=("${!WEIGHT_FILES[@]}")
+fi
+
+if ! command -v frame-omni-bencher > /dev/null; then
+    echo "错误: 未找到 frame-omni-bencher，请先执行 cargo install frame-omni-bencher"
+    exit 1
+fi
+
+echo "构建带基准测试的运行时..."
+cargo build --release -p minimal-template-runtime --features runtime-benchmarks
+
+for PALLET in "${PALLETS[@]}"; do
+    OUTPUT="${WEIGHT_FILES[$PALLET]}"
+    if [ -z "$OUTPUT" ]; then
+        echo "错误: 未知的 pallet $PALLET"
+        exit 1
+    fi
+
+    echo "运行 $PALLET 的基准测试..."
+    frame-omni-bencher v1 benchmark pallet \
+        --runtime "$RUNTIME_WASM" \
+        --pallet "$PALLET" \
+        --extrinsic "" \
+        --steps 50 \
+        --repeat 20 \
+        --output "$OUTPUT"
+    echo "已写入: $OUTPUT"
+done